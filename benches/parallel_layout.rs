@@ -0,0 +1,94 @@
+use std::time::{Duration, Instant};
+
+use compose_rt::{Composer, Recomposer, Root};
+use compose_taffy::impls::{LayoutNode, TaffyConfig};
+use compose_taffy::modifier::{modifier, StyleModifier};
+use compose_taffy::widgets::{column, leaf, row};
+use compose_taffy::{ParallelLayout, TaffyLayout};
+use taffy::{AvailableSpace, FlexWrap, Size};
+
+type Scope<T> = compose_taffy::impls::Scope<T, ()>;
+
+const PANELS: usize = 256;
+const ROWS: usize = 16;
+const CELLS: usize = 16;
+const ITERATIONS: u32 = 10;
+
+/// A wide dashboard of independent fixed size panels.
+fn dashboard(s: Scope<Root>) {
+    row(s, modifier().width(4000.0).flex_wrap(FlexWrap::Wrap), |s| {
+        for panel in 0..PANELS {
+            s.key(panel, |s| {
+                column(s, modifier().size(240.0, 320.0).padding(4.0), |s| {
+                    for _ in 0..ROWS {
+                        row(s, modifier().flex_grow(1.0).gap(2.0), |s| {
+                            for cell in 0..CELLS {
+                                leaf(
+                                    s,
+                                    modifier()
+                                        .flex_grow((cell % 3 + 1) as f32)
+                                        .min_height(4.0)
+                                        .margin(1.0),
+                                );
+                            }
+                        });
+                    }
+                });
+            });
+        }
+    });
+}
+
+fn available_space() -> Size<AvailableSpace> {
+    Size {
+        width: AvailableSpace::Definite(4000.0),
+        height: AvailableSpace::MaxContent,
+    }
+}
+
+/// Times the first layout of freshly composed trees.
+fn measure<F>(mut layout: F) -> Duration
+where
+    F: FnMut(&mut Recomposer<(), LayoutNode<()>>),
+{
+    let mut total = Duration::ZERO;
+    for _ in 0..ITERATIONS {
+        let mut recomposer = Composer::compose(dashboard, TaffyConfig::default());
+        let start = Instant::now();
+        layout(&mut recomposer);
+        total += start.elapsed();
+    }
+    total / ITERATIONS
+}
+
+fn main() {
+    let mut serial = Composer::compose(dashboard, TaffyConfig::default());
+    serial.compute_layout(available_space()).unwrap();
+    let mut parallel = Composer::compose(dashboard, TaffyConfig::default());
+    parallel.compute_layout_parallel(available_space()).unwrap();
+    assert_eq!(
+        serial.layout_snapshot().unwrap(),
+        parallel.layout_snapshot().unwrap(),
+        "parallel layout differs from serial layout"
+    );
+
+    let serial = measure(|recomposer| {
+        recomposer.compute_layout(available_space()).unwrap();
+    });
+    let parallel = measure(|recomposer| {
+        recomposer
+            .compute_layout_parallel(available_space())
+            .unwrap();
+    });
+    println!(
+        "{PANELS} panels of {} nodes on {} threads",
+        1 + ROWS * (1 + CELLS),
+        rayon::current_num_threads()
+    );
+    println!("serial:   {serial:?}");
+    println!("parallel: {parallel:?}");
+    println!(
+        "speedup:  {:.2}x",
+        serial.as_secs_f64() / parallel.as_secs_f64()
+    );
+}
@@ -1,55 +1,11 @@
 use compose_rt::{Composer, Root};
-use compose_taffy::impls::{LayoutNode, TaffyConfig};
+use compose_taffy::impls::TaffyConfig;
+use compose_taffy::widgets::{container, leaf};
 use compose_taffy::TaffyLayout;
 use taffy::{AvailableSpace, Dimension, JustifyContent, Size, Style};
 
 type Scope<T> = compose_taffy::impls::Scope<T, ()>;
 
-struct Container;
-
-#[track_caller]
-fn container<P, C>(s: Scope<P>, style: Style, content: C)
-where
-    P: 'static,
-    C: Fn(Scope<Container>) + Clone + 'static,
-{
-    let scope = s.child::<Container>();
-    s.create_node(
-        scope,
-        content,
-        move || style.clone(),
-        |style, _| LayoutNode::new(style),
-        |n, style, _| {
-            if n.style != style {
-                n.style = style;
-                n.mark_dirty();
-            }
-        },
-    );
-}
-
-struct Leaf;
-
-#[track_caller]
-fn leaf<P>(s: Scope<P>, style: Style)
-where
-    P: 'static,
-{
-    let scope = s.child::<Leaf>();
-    s.create_node(
-        scope,
-        |_| {},
-        move || style.clone(),
-        |style, _| LayoutNode::new(style),
-        |n, style, _| {
-            if n.style != style {
-                n.style = style;
-                n.mark_dirty();
-            }
-        },
-    );
-}
-
 fn app(s: Scope<Root>) {
     container(
         s,
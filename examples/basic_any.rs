@@ -1,86 +1,79 @@
 use compose_rt::{Composer, Root};
 use compose_taffy::impls::{LayoutNode, TaffyConfig};
+use compose_taffy::modifier::{auto, modifier, percent, StyleModifier};
+use compose_taffy::traits::Measure;
+use compose_taffy::widgets::{container, leaf};
 use compose_taffy::TaffyLayout;
-use taffy::{AvailableSpace, Dimension, JustifyContent, Size, Style};
+use taffy::{AlignSelf, AvailableSpace, Size};
 
-pub trait NodeContext {}
+pub trait NodeContext: Measure {}
 
-type Scope<T> = compose_taffy::impls::Scope<T, Box<dyn NodeContext>>;
-
-struct Container;
+struct Image {
+    width: f32,
+    height: f32,
+}
 
-#[track_caller]
-fn container<P, C>(s: Scope<P>, style: Style, content: C)
-where
-    P: 'static,
-    C: Fn(Scope<Container>) + Clone + 'static,
-{
-    let scope = s.child::<Container>();
-    s.create_node(
-        scope,
-        content,
-        move || style.clone(),
-        |style, _| LayoutNode::new(style),
-        |n, style, _| {
-            if n.style != style {
-                n.style = style;
-                n.mark_dirty();
-            }
-        },
-    );
+impl Measure for Image {
+    fn measure(
+        &mut self,
+        known_dimensions: Size<Option<f32>>,
+        _available_space: Size<AvailableSpace>,
+    ) -> Size<f32> {
+        match (known_dimensions.width, known_dimensions.height) {
+            (Some(width), Some(height)) => Size { width, height },
+            (Some(width), None) => Size {
+                width,
+                height: width / self.width * self.height,
+            },
+            (None, Some(height)) => Size {
+                width: height / self.height * self.width,
+                height,
+            },
+            (None, None) => Size {
+                width: self.width,
+                height: self.height,
+            },
+        }
+    }
 }
 
-struct Leaf;
+impl NodeContext for Image {}
+
+type Scope<T> = compose_taffy::impls::Scope<T, Box<dyn NodeContext>>;
+
+struct ImageLeaf;
 
 #[track_caller]
-fn leaf<P>(s: Scope<P>, style: Style)
+fn image<P>(s: Scope<P>, width: f32, height: f32)
 where
     P: 'static,
 {
-    let scope = s.child::<Leaf>();
+    let scope = s.child::<ImageLeaf>();
     s.create_node(
         scope,
         |_| {},
-        move || style.clone(),
-        |style, _| LayoutNode::new(style),
-        |n, style, _| {
-            if n.style != style {
-                n.style = style;
-                n.mark_dirty();
-            }
+        move || (width, height),
+        |(width, height), _| {
+            let style = modifier().align_self(AlignSelf::FlexStart).flex_shrink(0.0);
+            LayoutNode::with_context(style, Box::new(Image { width, height }))
+        },
+        |n, (width, height), _| {
+            n.context = Some(Box::new(Image { width, height }));
+            n.mark_dirty();
         },
     );
 }
 
 fn app(s: Scope<Root>) {
-    container(
-        s,
-        Style {
-            size: Size {
-                width: Dimension::Length(100.0),
-                height: Dimension::Length(100.0),
-            },
-            justify_content: Some(JustifyContent::Center),
-            ..Default::default()
-        },
-        |s| {
-            leaf(
-                s,
-                Style {
-                    size: Size {
-                        width: Dimension::Percent(0.5),
-                        height: Dimension::Auto,
-                    },
-                    ..Default::default()
-                },
-            );
-        },
-    );
+    container(s, modifier().size(100.0, 100.0).justify_center(), |s| {
+        leaf(s, modifier().width(percent(0.5)).height(auto()));
+        image(s, 40.0, 20.0);
+    });
 }
 
 fn main() {
     let mut recomposer = Composer::compose(app, TaffyConfig::default());
-    let _ = recomposer.compute_layout(Size {
+    let _ = recomposer.compute_layout_measured(Size {
         height: AvailableSpace::Definite(100.0),
         width: AvailableSpace::Definite(100.0),
     });
@@ -0,0 +1,52 @@
+use compose_rt::{Composer, Recomposer, Root};
+use compose_taffy::impls::{LayoutNode, TaffyConfig};
+use compose_taffy::modifier::{modifier, StyleModifier};
+use compose_taffy::widgets::{column, leaf, row, sized, with_constraints, Container};
+use compose_taffy::TaffyLayout;
+use taffy::{AvailableSpace, Size};
+
+type Scope<T> = compose_taffy::impls::Scope<T, ()>;
+
+fn app(s: Scope<Root>) {
+    with_constraints(s, modifier().fill_max_size(), |s, constraints| {
+        let wide = matches!(constraints.width, AvailableSpace::Definite(width) if width >= 200.0);
+        let content = |s: Scope<Container>| {
+            sized(s, modifier().flex_grow(1.0).height(40.0), |s, size| {
+                if size.get().width >= 180.0 {
+                    leaf(s, modifier().size(80.0, 40.0));
+                    leaf(s, modifier().size(80.0, 40.0));
+                } else {
+                    leaf(s, modifier().size(40.0, 40.0));
+                }
+            });
+            leaf(s, modifier().size(100.0, 40.0));
+        };
+        if wide {
+            row(s, modifier().fill_max_size(), content);
+        } else {
+            column(s, modifier().fill_max_size(), content);
+        }
+    });
+}
+
+fn layout(recomposer: &mut Recomposer<(), LayoutNode<()>>, width: f32) {
+    let available_space = Size {
+        width: AvailableSpace::Definite(width),
+        height: AvailableSpace::Definite(200.0),
+    };
+    // recompose until the content reacts no more to its layout
+    while !recomposer
+        .compute_layout(available_space)
+        .unwrap()
+        .is_empty()
+    {
+        recomposer.recompose();
+    }
+    recomposer.print_layout_tree().unwrap();
+}
+
+fn main() {
+    let mut recomposer = Composer::compose(app, TaffyConfig::default());
+    layout(&mut recomposer, 300.0);
+    layout(&mut recomposer, 150.0);
+}
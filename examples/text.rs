@@ -0,0 +1,45 @@
+use compose_rt::{Composer, Root};
+use compose_taffy::impls::TaffyConfig;
+use compose_taffy::text::{measure_text_with, MonospaceMeasurer, TextContext, TextStyle};
+use compose_taffy::widgets::{column, text};
+use compose_taffy::TaffyLayout;
+use taffy::{AvailableSpace, Dimension, Size, Style};
+
+type Scope<T> = compose_taffy::impls::Scope<T, TextContext>;
+
+fn app(s: Scope<Root>) {
+    column(
+        s,
+        Style {
+            size: Size {
+                width: Dimension::Length(120.0),
+                height: Dimension::Auto,
+            },
+            ..Default::default()
+        },
+        |s| {
+            text(s, Style::default(), "Title", TextStyle::new(20.0));
+            text(
+                s,
+                Style::default(),
+                "A longer paragraph which wraps to the width of its container.",
+                TextStyle::default(),
+            );
+        },
+    );
+}
+
+fn main() {
+    let mut recomposer = Composer::compose(app, TaffyConfig::default());
+    let mut measurer = MonospaceMeasurer::default();
+    let root = recomposer.root_node_key();
+    let _ = recomposer.compute_layout_with(
+        Size {
+            height: AvailableSpace::MaxContent,
+            width: AvailableSpace::Definite(200.0),
+        },
+        root,
+        measure_text_with(&mut measurer),
+    );
+    let _ = recomposer.print_layout_tree();
+}
@@ -0,0 +1,361 @@
+use compose_rt::{Composer, NodeKey};
+use taffy::{Layout, Point, Rect, Size};
+
+use crate::traits::TaffyNode;
+use crate::LayoutChange;
+
+/// Maps the linear progress of a transition to the eased progress, both in `0.0..=1.0`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Easing {
+    #[default]
+    Linear,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+    /// A CSS `cubic-bezier(x1, y1, x2, y2)` timing function
+    CubicBezier(f32, f32, f32, f32),
+}
+
+impl Easing {
+    pub fn apply(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match *self {
+            Easing::Linear => t,
+            Easing::EaseIn => cubic_bezier(0.42, 0.0, 1.0, 1.0, t),
+            Easing::EaseOut => cubic_bezier(0.0, 0.0, 0.58, 1.0, t),
+            Easing::EaseInOut => cubic_bezier(0.42, 0.0, 0.58, 1.0, t),
+            Easing::CubicBezier(x1, y1, x2, y2) => cubic_bezier(x1, y1, x2, y2, t),
+        }
+    }
+}
+
+/// Evaluates the y of the curve at `x` by solving for its parameter with Newton's method,
+/// falling back to bisection where the slope is too flat.
+fn cubic_bezier(x1: f32, y1: f32, x2: f32, y2: f32, x: f32) -> f32 {
+    let bezier = |p1: f32, p2: f32, t: f32| {
+        let u = 1.0 - t;
+        3.0 * u * u * t * p1 + 3.0 * u * t * t * p2 + t * t * t
+    };
+    let slope = |p1: f32, p2: f32, t: f32| {
+        let u = 1.0 - t;
+        3.0 * u * u * p1 + 6.0 * u * t * (p2 - p1) + 3.0 * t * t * (1.0 - p2)
+    };
+    let mut t = x;
+    for _ in 0..8 {
+        let error = bezier(x1, x2, t) - x;
+        if error.abs() < 1e-5 {
+            return bezier(y1, y2, t);
+        }
+        let d = slope(x1, x2, t);
+        if d.abs() < 1e-6 {
+            break;
+        }
+        t -= error / d;
+    }
+    let (mut lo, mut hi) = (0.0, 1.0);
+    t = x;
+    for _ in 0..32 {
+        let value = bezier(x1, x2, t);
+        if (value - x).abs() < 1e-5 {
+            break;
+        }
+        if value < x {
+            lo = t;
+        } else {
+            hi = t;
+        }
+        t = (lo + hi) / 2.0;
+    }
+    bezier(y1, y2, t)
+}
+
+/// How a node animates from its previous layout when a layout pass changes it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LayoutTransition {
+    /// The duration in the time unit passed to `TaffyLayout::tick`
+    pub duration: f32,
+    pub easing: Easing,
+}
+
+impl LayoutTransition {
+    #[inline(always)]
+    pub fn new(duration: f32) -> Self {
+        Self {
+            duration,
+            easing: Easing::default(),
+        }
+    }
+
+    #[inline(always)]
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+}
+
+/// A running transition between two layouts of a node.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LayoutAnimation {
+    pub from: Layout,
+    pub to: Layout,
+    pub transition: LayoutTransition,
+    pub elapsed: f32,
+}
+
+impl LayoutAnimation {
+    #[inline(always)]
+    pub fn new(from: Layout, to: Layout, transition: LayoutTransition) -> Self {
+        Self {
+            from,
+            to,
+            transition,
+            elapsed: 0.0,
+        }
+    }
+
+    /// The eased progress of the animation
+    pub fn progress(&self) -> f32 {
+        if self.is_finished() {
+            return 1.0;
+        }
+        self.transition
+            .easing
+            .apply(self.elapsed / self.transition.duration)
+    }
+
+    #[inline(always)]
+    pub fn is_finished(&self) -> bool {
+        self.elapsed >= self.transition.duration
+    }
+
+    /// The layout interpolated at the current progress
+    pub fn layout(&self) -> Layout {
+        let t = self.progress();
+        Layout {
+            location: lerp_point(self.from.location, self.to.location, t),
+            size: lerp_size(self.from.size, self.to.size, t),
+            border: lerp_rect(self.from.border, self.to.border, t),
+            padding: lerp_rect(self.from.padding, self.to.padding, t),
+            margin: lerp_rect(self.from.margin, self.to.margin, t),
+            ..self.to
+        }
+    }
+}
+
+#[inline(always)]
+fn lerp(from: f32, to: f32, t: f32) -> f32 {
+    from + (to - from) * t
+}
+
+#[inline(always)]
+fn lerp_point(from: Point<f32>, to: Point<f32>, t: f32) -> Point<f32> {
+    Point {
+        x: lerp(from.x, to.x, t),
+        y: lerp(from.y, to.y, t),
+    }
+}
+
+#[inline(always)]
+fn lerp_size(from: Size<f32>, to: Size<f32>, t: f32) -> Size<f32> {
+    Size {
+        width: lerp(from.width, to.width, t),
+        height: lerp(from.height, to.height, t),
+    }
+}
+
+#[inline(always)]
+fn lerp_rect(from: Rect<f32>, to: Rect<f32>, t: f32) -> Rect<f32> {
+    Rect {
+        left: lerp(from.left, to.left, t),
+        right: lerp(from.right, to.right, t),
+        top: lerp(from.top, to.top, t),
+        bottom: lerp(from.bottom, to.bottom, t),
+    }
+}
+
+/// Starts the transitions of the changed nodes, a running animation is retargeted from
+/// its current layout. Nodes without a previous layout are not animated.
+pub(crate) fn start_transitions<T>(composer: &mut Composer<T>, changes: &[LayoutChange])
+where
+    T: TaffyNode,
+{
+    for change in changes {
+        let Some(data) = composer.nodes[change.node_key].data.as_mut() else {
+            continue;
+        };
+        let Some(transition) = data.get_transition() else {
+            continue;
+        };
+        let from = match data.get_animation() {
+            Some(animation) => animation.layout(),
+            None if change.previous == Layout::new() => continue,
+            None => change.previous,
+        };
+        data.set_animation(Some(LayoutAnimation::new(from, change.current, transition)));
+    }
+}
+
+/// Advances every running animation, returns the nodes whose animated layout changed.
+pub(crate) fn advance_animations<T>(composer: &mut Composer<T>, dt: f32) -> Vec<NodeKey>
+where
+    T: TaffyNode,
+{
+    let mut animated = Vec::new();
+    for (node_key, node) in composer.nodes.iter_mut() {
+        let Some(data) = node.data.as_mut() else {
+            continue;
+        };
+        let Some(mut animation) = data.get_animation().copied() else {
+            continue;
+        };
+        animation.elapsed += dt;
+        animated.push(node_key);
+        data.set_animation((!animation.is_finished()).then_some(animation));
+    }
+    animated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPSILON: f32 = 1e-4;
+
+    fn assert_close(actual: f32, expected: f32) {
+        assert!(
+            (actual - expected).abs() < EPSILON,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    const EASINGS: [Easing; 5] = [
+        Easing::Linear,
+        Easing::EaseIn,
+        Easing::EaseOut,
+        Easing::EaseInOut,
+        Easing::CubicBezier(0.25, 0.1, 0.25, 1.0),
+    ];
+
+    #[test]
+    fn easings_start_at_zero_and_end_at_one() {
+        for easing in EASINGS {
+            assert_close(easing.apply(0.0), 0.0);
+            assert_close(easing.apply(1.0), 1.0);
+        }
+    }
+
+    #[test]
+    fn progress_is_clamped() {
+        for easing in EASINGS {
+            assert_close(easing.apply(-0.5), 0.0);
+            assert_close(easing.apply(1.5), 1.0);
+        }
+    }
+
+    #[test]
+    fn easings_are_monotonic() {
+        for easing in EASINGS {
+            let mut previous = 0.0;
+            for step in 1..=100 {
+                let value = easing.apply(step as f32 / 100.0);
+                assert!(
+                    value >= previous - EPSILON,
+                    "{easing:?} decreases at step {step}"
+                );
+                previous = value;
+            }
+        }
+    }
+
+    #[test]
+    fn ease_in_starts_slow_and_ease_out_starts_fast() {
+        for t in [0.25, 0.5, 0.75] {
+            assert!(Easing::EaseIn.apply(t) < t);
+            assert!(Easing::EaseOut.apply(t) > t);
+        }
+    }
+
+    #[test]
+    fn ease_in_out_is_symmetric() {
+        assert_close(Easing::EaseInOut.apply(0.5), 0.5);
+        for t in [0.1, 0.25, 0.4] {
+            assert_close(
+                Easing::EaseInOut.apply(t) + Easing::EaseInOut.apply(1.0 - t),
+                1.0,
+            );
+        }
+    }
+
+    #[test]
+    fn cubic_bezier_matches_css_ease() {
+        // CSS `ease` is `cubic-bezier(0.25, 0.1, 0.25, 1)`
+        let ease = Easing::CubicBezier(0.25, 0.1, 0.25, 1.0);
+        assert_close(ease.apply(0.5), 0.802_403_4);
+    }
+
+    #[test]
+    fn linear_cubic_bezier_is_the_identity() {
+        let linear = Easing::CubicBezier(0.0, 0.0, 1.0, 1.0);
+        for t in [0.1, 0.3, 0.5, 0.7, 0.9] {
+            assert_close(linear.apply(t), t);
+        }
+    }
+
+    #[test]
+    fn cubic_bezier_falls_back_to_bisection() {
+        // the x curve is nearly flat around the middle, where Newton's method overshoots
+        let curve = Easing::CubicBezier(1.0, 0.0, 0.0, 1.0);
+        assert_close(curve.apply(0.45), 0.176_880_8);
+        assert_close(curve.apply(0.55), 0.823_119_2);
+    }
+
+    #[cfg(feature = "flexbox")]
+    mod layout {
+        use compose_rt::Root;
+
+        use crate::impls::Scope;
+        use crate::modifier::{modifier, StyleModifier};
+        use crate::test_utils::{children, compose_layout, point, SPACE};
+        use crate::widgets::{animated, column, leaf};
+        use crate::{LayoutTransition, TaffyLayout};
+
+        #[test]
+        fn moved_node_animates_to_its_new_location() {
+            fn app(s: Scope<Root, ()>) {
+                column(s, modifier().size(100.0, 100.0), |s| {
+                    leaf(s, modifier().height(20.0));
+                    animated(
+                        s,
+                        modifier().height(10.0),
+                        LayoutTransition::new(1.0),
+                        |_| {},
+                    );
+                });
+            }
+            let mut recomposer = compose_layout(app);
+            let [first, moved] = children(&recomposer, recomposer.root_node_key())[..] else {
+                panic!("expected two children");
+            };
+            // the first layout of a node is not animated
+            assert!(recomposer.tick(0.5).is_empty());
+
+            recomposer.with_composer_mut(|composer| {
+                let data = composer.nodes[first].data.as_mut().unwrap();
+                data.set_style(modifier().height(60.0));
+            });
+            recomposer.compute_layout(SPACE).unwrap();
+            assert_eq!(recomposer.layout(moved).unwrap().location, point(0.0, 60.0));
+            assert_eq!(recomposer.animated_layout(moved).unwrap().location.y, 20.0);
+
+            assert_eq!(recomposer.tick(0.5), vec![moved]);
+            assert_eq!(recomposer.animated_layout(moved).unwrap().location.y, 40.0);
+
+            assert_eq!(recomposer.tick(0.5), vec![moved]);
+            assert_eq!(
+                recomposer.animated_layout(moved).unwrap(),
+                recomposer.layout(moved).unwrap()
+            );
+            assert!(recomposer.tick(0.5).is_empty());
+        }
+    }
+}
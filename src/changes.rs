@@ -0,0 +1,67 @@
+use compose_rt::{ComposeNode, Composer, NodeKey, State};
+use taffy::Layout;
+
+use crate::geometry::selected_layout;
+use crate::traits::{TaffyConfig, TaffyNode};
+
+/// A node whose final layout changed in a layout pass.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LayoutChange {
+    pub node_key: NodeKey,
+    /// The layout before the pass, zero sized for nodes laid out for the first time
+    pub previous: Layout,
+    pub current: Layout,
+}
+
+impl LayoutChange {
+    /// Returns whether the node moved relative to its parent
+    #[inline(always)]
+    pub fn moved(&self) -> bool {
+        self.previous.location != self.current.location
+    }
+
+    /// Returns whether the size of the node changed
+    #[inline(always)]
+    pub fn resized(&self) -> bool {
+        self.previous.size != self.current.size
+    }
+}
+
+/// Compares the layouts recorded before a pass against the ones selected after it.
+pub(crate) fn layout_changes<T>(
+    composer: &Composer<T>,
+    previous_layouts: Vec<(NodeKey, Layout)>,
+) -> Vec<LayoutChange>
+where
+    T: TaffyNode,
+    T::Context: TaffyConfig,
+{
+    previous_layouts
+        .into_iter()
+        .filter_map(|(node_key, previous)| {
+            let data = composer.nodes[node_key].data.as_ref()?;
+            let current = *selected_layout(composer, data);
+            (previous != current).then_some(LayoutChange {
+                node_key,
+                previous,
+                current,
+            })
+        })
+        .collect()
+}
+
+/// States paired with the values to publish to them.
+pub(crate) type StateUpdates<V, T> = Vec<(State<V, T>, V)>;
+
+/// Sets the states whose value differs, which must happen once the composer is released.
+pub(crate) fn publish<V, T>(updates: StateUpdates<V, T>)
+where
+    V: PartialEq + Clone + 'static,
+    T: ComposeNode,
+{
+    for (state, value) in updates {
+        if state.get_untracked() != value {
+            state.set(value);
+        }
+    }
+}
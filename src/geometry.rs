@@ -0,0 +1,264 @@
+use compose_rt::{Composer, NodeKey};
+use taffy::{Display, Layout, Point, Rect, Size};
+
+use crate::layout::resolve_display;
+use crate::traits::{TaffyConfig, TaffyNode};
+use crate::LayoutError;
+
+/// The layout of a node positioned in the coordinate space of its layout root.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AbsoluteLayout {
+    /// The absolute top-left corner of the node's border box
+    pub location: Point<f32>,
+    /// The layout of the node relative to its parent
+    pub layout: Layout,
+}
+
+impl AbsoluteLayout {
+    #[inline(always)]
+    pub fn new(location: Point<f32>, layout: Layout) -> Self {
+        Self { location, layout }
+    }
+
+    #[inline(always)]
+    pub fn size(&self) -> Size<f32> {
+        self.layout.size
+    }
+
+    /// The edges of the border box of the node
+    #[inline(always)]
+    pub fn border_box(&self) -> Rect<f32> {
+        Rect {
+            left: self.location.x,
+            right: self.location.x + self.layout.size.width,
+            top: self.location.y,
+            bottom: self.location.y + self.layout.size.height,
+        }
+    }
+
+    /// The edges of the padding box of the node, which is the border box without the borders
+    #[inline(always)]
+    pub fn padding_box(&self) -> Rect<f32> {
+        inset(self.border_box(), self.layout.border)
+    }
+
+    /// The edges of the content box of the node, which is the padding box without the padding
+    #[inline(always)]
+    pub fn content_box(&self) -> Rect<f32> {
+        inset(self.padding_box(), self.layout.padding)
+    }
+
+    /// Returns whether the point lies inside the border box of the node
+    #[inline(always)]
+    pub fn contains(&self, point: Point<f32>) -> bool {
+        contains(self.border_box(), point)
+    }
+}
+
+#[inline(always)]
+pub(crate) fn inset(rect: Rect<f32>, by: Rect<f32>) -> Rect<f32> {
+    Rect {
+        left: rect.left + by.left,
+        right: rect.right - by.right,
+        top: rect.top + by.top,
+        bottom: rect.bottom - by.bottom,
+    }
+}
+
+#[inline(always)]
+pub(crate) fn contains(rect: Rect<f32>, point: Point<f32>) -> bool {
+    point.x >= rect.left && point.x < rect.right && point.y >= rect.top && point.y < rect.bottom
+}
+
+/// Returns the layout of the node selected by `TaffyConfig::use_rounding`.
+pub(crate) fn node_layout<T>(
+    composer: &Composer<T>,
+    node_key: NodeKey,
+) -> Result<&Layout, LayoutError>
+where
+    T: TaffyNode,
+    T::Context: TaffyConfig,
+{
+    let data = node_data(composer, node_key)?;
+    Ok(selected_layout(composer, data))
+}
+
+pub(crate) fn node_data<T>(composer: &Composer<T>, node_key: NodeKey) -> Result<&T, LayoutError>
+where
+    T: TaffyNode,
+{
+    composer
+        .nodes
+        .get(node_key)
+        .ok_or(LayoutError::InvalidInputNode(node_key))?
+        .data
+        .as_ref()
+        .ok_or(LayoutError::MissingNodeData(node_key))
+}
+
+#[inline(always)]
+pub(crate) fn selected_layout<'a, T>(composer: &Composer<T>, data: &'a T) -> &'a Layout
+where
+    T: TaffyNode,
+    T::Context: TaffyConfig,
+{
+    if composer.context.use_rounding() {
+        data.get_final_layout()
+    } else {
+        data.get_unrounded_layout()
+    }
+}
+
+/// Returns whether the node is hidden, by `Display::None` or by `DisplayFallback::Hidden`.
+#[inline(always)]
+pub(crate) fn is_hidden<T>(composer: &Composer<T>, data: &T) -> bool
+where
+    T: TaffyNode,
+    T::Context: TaffyConfig,
+{
+    let fallback = composer.context.display_fallback();
+    resolve_display(data.get_display(), fallback) == Some(Display::None)
+}
+
+/// Returns whether the node is laid out inside its parent, overlays are separate layout
+/// roots.
+#[inline(always)]
+pub(crate) fn is_layout_child<T>(composer: &Composer<T>, node_key: NodeKey) -> bool
+where
+    T: TaffyNode,
+{
+    let data = composer.nodes.get(node_key).and_then(|n| n.data.as_ref());
+    !data.is_some_and(|data| data.is_overlay())
+}
+
+/// Returns the children laid out inside the node, which are its children without the
+/// overlays.
+#[inline(always)]
+pub(crate) fn layout_children<T>(
+    composer: &Composer<T>,
+    node_key: NodeKey,
+) -> impl DoubleEndedIterator<Item = NodeKey> + '_
+where
+    T: TaffyNode,
+{
+    composer.nodes[node_key]
+        .children
+        .iter()
+        .copied()
+        .filter(move |&child_key| is_layout_child(composer, child_key))
+}
+
+/// Returns the parent the node is laid out in, `None` for the root and overlays.
+#[inline(always)]
+pub(crate) fn layout_parent<T>(composer: &Composer<T>, node_key: NodeKey) -> Option<NodeKey>
+where
+    T: TaffyNode,
+{
+    let node = &composer.nodes[node_key];
+    let is_overlay = node.data.as_ref().is_some_and(|data| data.is_overlay());
+    (node.parent != node_key && !is_overlay).then_some(node.parent)
+}
+
+/// Accumulates the locations and scroll offsets of the ancestors of the node.
+pub(crate) fn absolute_layout<T>(
+    composer: &Composer<T>,
+    node_key: NodeKey,
+) -> Result<AbsoluteLayout, LayoutError>
+where
+    T: TaffyNode,
+    T::Context: TaffyConfig,
+{
+    let layout = *node_layout(composer, node_key)?;
+    let mut location = layout.location;
+    let mut current = node_key;
+    while let Some(parent) = layout_parent(composer, current) {
+        let Some(parent_data) = composer.nodes.get(parent).and_then(|n| n.data.as_ref()) else {
+            break;
+        };
+        let parent_location = selected_layout(composer, parent_data).location;
+        let scroll_offset = parent_data.get_scroll_offset();
+        location.x += parent_location.x - scroll_offset.x;
+        location.y += parent_location.y - scroll_offset.y;
+        current = parent;
+    }
+    Ok(AbsoluteLayout::new(location, layout))
+}
+
+/// Collects the absolute layouts of the subtree in pre-order, skipping the
+/// descendants of nodes with `Display::None` and the overlays.
+pub(crate) fn absolute_layouts<T>(
+    composer: &Composer<T>,
+    node_key: NodeKey,
+) -> Result<Vec<(NodeKey, AbsoluteLayout)>, LayoutError>
+where
+    T: TaffyNode,
+    T::Context: TaffyConfig,
+{
+    let root = absolute_layout(composer, node_key)?;
+    let mut layouts = Vec::new();
+    collect_absolute_layouts(composer, node_key, root, &mut layouts);
+    Ok(layouts)
+}
+
+fn collect_absolute_layouts<T>(
+    composer: &Composer<T>,
+    node_key: NodeKey,
+    absolute: AbsoluteLayout,
+    layouts: &mut Vec<(NodeKey, AbsoluteLayout)>,
+) where
+    T: TaffyNode,
+    T::Context: TaffyConfig,
+{
+    layouts.push((node_key, absolute));
+    let node = &composer.nodes[node_key];
+    let Some(data) = node.data.as_ref() else {
+        return;
+    };
+    if is_hidden(composer, data) {
+        return;
+    }
+    let scroll_offset = data.get_scroll_offset();
+    for child_key in layout_children(composer, node_key) {
+        let Some(child_data) = composer.nodes.get(child_key).and_then(|n| n.data.as_ref()) else {
+            continue;
+        };
+        let layout = *selected_layout(composer, child_data);
+        let location = Point {
+            x: absolute.location.x + layout.location.x - scroll_offset.x,
+            y: absolute.location.y + layout.location.y - scroll_offset.y,
+        };
+        collect_absolute_layouts(
+            composer,
+            child_key,
+            AbsoluteLayout::new(location, layout),
+            layouts,
+        );
+    }
+}
+
+/// Returns the node followed by the overlays inside its subtree in composition order,
+/// which is their z-order. Overlays inside subtrees with `Display::None` are skipped.
+pub(crate) fn layout_roots<T>(composer: &Composer<T>, node_key: NodeKey) -> Vec<NodeKey>
+where
+    T: TaffyNode,
+    T::Context: TaffyConfig,
+{
+    let mut roots = vec![node_key];
+    let mut stack = vec![node_key];
+    while let Some(node_key) = stack.pop() {
+        let Some(node) = composer.nodes.get(node_key) else {
+            continue;
+        };
+        let Some(data) = node.data.as_ref() else {
+            continue;
+        };
+        if is_hidden(composer, data) {
+            continue;
+        }
+        if data.is_overlay() && node_key != roots[0] {
+            roots.push(node_key);
+        }
+        stack.extend(node.children.iter().rev());
+    }
+    roots
+}
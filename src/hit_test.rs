@@ -0,0 +1,264 @@
+use compose_rt::{Composer, NodeKey};
+use taffy::{CoreStyle, Overflow, Point, Rect};
+
+use crate::geometry::{
+    absolute_layout, contains, is_hidden, layout_children, layout_parent, selected_layout,
+};
+use crate::traits::{TaffyConfig, TaffyNode};
+use crate::{AbsoluteLayout, LayoutError};
+
+const UNCLIPPED: Rect<f32> = Rect {
+    left: f32::NEG_INFINITY,
+    right: f32::INFINITY,
+    top: f32::NEG_INFINITY,
+    bottom: f32::INFINITY,
+};
+
+/// Returns the path from `node_key` down to the topmost node containing the
+/// point, or an empty path if nothing in the subtree was hit.
+pub(crate) fn hit_test_path<T>(
+    composer: &Composer<T>,
+    node_key: NodeKey,
+    point: Point<f32>,
+) -> Result<Vec<NodeKey>, LayoutError>
+where
+    T: TaffyNode,
+    T::Context: TaffyConfig,
+{
+    let absolute = absolute_layout(composer, node_key)?;
+    let clip = ancestor_clip(composer, node_key)?;
+    let mut path = Vec::new();
+    hit_test_node(composer, node_key, absolute, clip, point, &mut path);
+    path.reverse();
+    Ok(path)
+}
+
+/// Intersects the clip rects of all ancestors of the node.
+fn ancestor_clip<T>(composer: &Composer<T>, node_key: NodeKey) -> Result<Rect<f32>, LayoutError>
+where
+    T: TaffyNode,
+    T::Context: TaffyConfig,
+{
+    let mut clip = UNCLIPPED;
+    let mut current = node_key;
+    while let Some(parent) = layout_parent(composer, current) {
+        let Some(parent_data) = composer.nodes.get(parent).and_then(|n| n.data.as_ref()) else {
+            break;
+        };
+        let parent_absolute = absolute_layout(composer, parent)?;
+        clip = clip_children(parent_data, &parent_absolute, clip);
+        current = parent;
+    }
+    Ok(clip)
+}
+
+/// Restricts the clip rect to the padding box of the node along each axis whose
+/// overflow is not visible.
+fn clip_children<T>(data: &T, absolute: &AbsoluteLayout, clip: Rect<f32>) -> Rect<f32>
+where
+    T: TaffyNode,
+{
+    let overflow = data.get_core_container_style().overflow();
+    let padding_box = absolute.padding_box();
+    let mut clip = clip;
+    if overflow.x != Overflow::Visible {
+        clip.left = clip.left.max(padding_box.left);
+        clip.right = clip.right.min(padding_box.right);
+    }
+    if overflow.y != Overflow::Visible {
+        clip.top = clip.top.max(padding_box.top);
+        clip.bottom = clip.bottom.min(padding_box.bottom);
+    }
+    clip
+}
+
+/// Pushes the hit path in reverse order, the deepest node first.
+fn hit_test_node<T>(
+    composer: &Composer<T>,
+    node_key: NodeKey,
+    absolute: AbsoluteLayout,
+    clip: Rect<f32>,
+    point: Point<f32>,
+    path: &mut Vec<NodeKey>,
+) -> bool
+where
+    T: TaffyNode,
+    T::Context: TaffyConfig,
+{
+    let node = &composer.nodes[node_key];
+    let Some(data) = node.data.as_ref() else {
+        return false;
+    };
+    if is_hidden(composer, data) {
+        return false;
+    }
+    let child_clip = clip_children(data, &absolute, clip);
+    let scroll_offset = data.get_scroll_offset();
+    // later children are painted on top of earlier ones
+    for child_key in layout_children(composer, node_key).rev() {
+        let Some(child_data) = composer.nodes.get(child_key).and_then(|n| n.data.as_ref()) else {
+            continue;
+        };
+        let layout = *selected_layout(composer, child_data);
+        let location = Point {
+            x: absolute.location.x + layout.location.x - scroll_offset.x,
+            y: absolute.location.y + layout.location.y - scroll_offset.y,
+        };
+        let child_absolute = AbsoluteLayout::new(location, layout);
+        if hit_test_node(composer, child_key, child_absolute, child_clip, point, path) {
+            path.push(node_key);
+            return true;
+        }
+    }
+    if contains(clip, point) && absolute.contains(point) {
+        path.push(node_key);
+        return true;
+    }
+    false
+}
+
+#[cfg(all(test, feature = "flexbox"))]
+mod tests {
+    use compose_rt::{NodeKey, Recomposer, Root};
+    use taffy::Overflow;
+
+    use crate::impls::{LayoutNode, Scope};
+    use crate::modifier::{modifier, StyleModifier};
+    use crate::test_utils::{compose_layout, point};
+    use crate::widgets::{container, leaf, overlay, scroll};
+    use crate::TaffyLayout;
+
+    /// A 200x200 root with absolutely positioned children:
+    /// - a 100x100 box clipping its child, which overflows it by 50 along both axes,
+    /// - a 40x40 leaf at (80, 80), on top of the overflowing child,
+    /// - a 50x50 scroll container at (0, 150) with a 50x20 leaf 60 down its content,
+    /// - an overlay with a 40x40 leaf at (150, 150).
+    fn app(s: Scope<Root, ()>) {
+        container(s, modifier().size(200.0, 200.0), |s| {
+            let at = |x: f32, y: f32| modifier().absolute().left(x).top(y);
+            container(
+                s,
+                at(0.0, 0.0).size(100.0, 100.0).overflow(Overflow::Hidden),
+                move |s| {
+                    leaf(s, at(50.0, 50.0).size(100.0, 100.0));
+                },
+            );
+            leaf(s, at(80.0, 80.0).size(40.0, 40.0));
+            scroll(s, at(0.0, 150.0).size(50.0, 50.0), move |s| {
+                leaf(s, at(0.0, 60.0).size(50.0, 20.0));
+            });
+            overlay(s, modifier(), move |s| {
+                leaf(s, at(150.0, 150.0).size(40.0, 40.0));
+            });
+        });
+    }
+
+    struct Nodes {
+        root: NodeKey,
+        clip: NodeKey,
+        overflowing: NodeKey,
+        top: NodeKey,
+        #[cfg_attr(not(feature = "content_size"), allow(dead_code))]
+        scroll: NodeKey,
+        #[cfg_attr(not(feature = "content_size"), allow(dead_code))]
+        scrolled: NodeKey,
+        overlay: NodeKey,
+        overlay_leaf: NodeKey,
+    }
+
+    fn compose() -> (Recomposer<(), LayoutNode<()>>, Nodes) {
+        let recomposer = compose_layout(app);
+        let root = recomposer.root_node_key();
+        let nodes = recomposer.with_composer(|composer| {
+            let children = |node_key: NodeKey| composer.nodes[node_key].children.clone();
+            let [clip, top, scroll, overlay] = children(root)[..] else {
+                panic!("expected four children");
+            };
+            Nodes {
+                root,
+                clip,
+                overflowing: children(clip)[0],
+                top,
+                scroll,
+                scrolled: children(scroll)[0],
+                overlay,
+                overlay_leaf: children(overlay)[0],
+            }
+        });
+        (recomposer, nodes)
+    }
+
+    #[test]
+    fn overflowing_child_is_hit_inside_the_clip() {
+        let (recomposer, nodes) = compose();
+        assert_eq!(
+            recomposer.hit_test_path(point(60.0, 60.0)).unwrap(),
+            vec![nodes.root, nodes.clip, nodes.overflowing]
+        );
+    }
+
+    #[test]
+    fn overflowing_child_is_clipped() {
+        let (recomposer, nodes) = compose();
+        assert_eq!(
+            recomposer.hit_test(point(125.0, 125.0)).unwrap(),
+            Some(nodes.root)
+        );
+        assert_eq!(
+            recomposer
+                .hit_test_with(nodes.clip, point(125.0, 125.0))
+                .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn later_siblings_are_on_top() {
+        let (recomposer, nodes) = compose();
+        assert_eq!(
+            recomposer.hit_test(point(90.0, 90.0)).unwrap(),
+            Some(nodes.top)
+        );
+        assert_eq!(
+            recomposer.hit_test(point(70.0, 70.0)).unwrap(),
+            Some(nodes.overflowing)
+        );
+    }
+
+    #[cfg(feature = "content_size")]
+    #[test]
+    fn scroll_offsets_shift_the_content() {
+        let (mut recomposer, nodes) = compose();
+        assert_eq!(
+            recomposer.hit_test(point(10.0, 185.0)).unwrap(),
+            Some(nodes.scroll)
+        );
+        let offset = recomposer
+            .set_scroll_offset(nodes.scroll, point(0.0, 30.0))
+            .unwrap();
+        assert_eq!(offset, point(0.0, 30.0));
+        assert_eq!(
+            recomposer.hit_test(point(10.0, 185.0)).unwrap(),
+            Some(nodes.scrolled)
+        );
+        assert_eq!(
+            recomposer.hit_test(point(10.0, 175.0)).unwrap(),
+            Some(nodes.scroll)
+        );
+    }
+
+    #[test]
+    fn overlays_are_hit_before_the_root() {
+        let (recomposer, nodes) = compose();
+        assert_eq!(
+            recomposer.hit_test_path(point(160.0, 160.0)).unwrap(),
+            vec![nodes.overlay, nodes.overlay_leaf]
+        );
+        assert_eq!(
+            recomposer
+                .hit_test_with(nodes.root, point(160.0, 160.0))
+                .unwrap(),
+            Some(nodes.root)
+        );
+    }
+}
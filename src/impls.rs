@@ -1,16 +1,61 @@
-use compose_rt::ComposeNode;
-use taffy::{Cache, Layout, Style};
+use std::cell::Cell;
+use std::cmp::Ordering;
+use std::fmt::{self, Debug, Formatter};
+use std::rc::Rc;
 
-use crate::traits;
+use compose_rt::{ComposeNode, NodeKey};
+#[cfg(feature = "detailed_layout_info")]
+use taffy::DetailedLayoutInfo;
+use taffy::{AvailableSpace, Cache, Layout, Point, Size, Style};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+use crate::rounding::valid_scale_factor;
+use crate::{traits, DisplayFallback, LayoutAnimation, LayoutTransition, PixelSnapping};
+
+#[derive(Debug, Clone, Copy)]
 pub struct TaffyConfig {
     pub use_rounding: bool,
+    pub display_fallback: DisplayFallback,
+    /// The number of device pixels per logical unit, e.g. `2.0` snaps to half units.
+    /// Values which are not positive finite numbers are treated as `1.0`.
+    pub scale_factor: f32,
+    pub pixel_snapping: PixelSnapping,
 }
 
 impl Default for TaffyConfig {
     fn default() -> Self {
-        Self { use_rounding: true }
+        Self {
+            use_rounding: true,
+            display_fallback: DisplayFallback::default(),
+            scale_factor: 1.0,
+            pixel_snapping: PixelSnapping::default(),
+        }
+    }
+}
+
+// compares the scale factor by `f32::total_cmp` to keep the config `Eq` and `Ord`
+impl PartialEq for TaffyConfig {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for TaffyConfig {}
+
+impl PartialOrd for TaffyConfig {
+    #[inline(always)]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for TaffyConfig {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.use_rounding
+            .cmp(&other.use_rounding)
+            .then(self.display_fallback.cmp(&other.display_fallback))
+            .then(self.scale_factor.total_cmp(&other.scale_factor))
+            .then(self.pixel_snapping.cmp(&other.pixel_snapping))
     }
 }
 
@@ -35,6 +80,39 @@ impl TaffyConfig {
     pub fn disable_rounding(&mut self) {
         self.use_rounding = false;
     }
+
+    #[inline(always)]
+    pub fn with_display_fallback(mut self, display_fallback: DisplayFallback) -> Self {
+        self.display_fallback = display_fallback;
+        self
+    }
+
+    #[inline(always)]
+    pub fn set_display_fallback(&mut self, display_fallback: DisplayFallback) {
+        self.display_fallback = display_fallback;
+    }
+
+    #[inline(always)]
+    pub fn with_scale_factor(mut self, scale_factor: f32) -> Self {
+        self.scale_factor = valid_scale_factor(scale_factor);
+        self
+    }
+
+    #[inline(always)]
+    pub fn set_scale_factor(&mut self, scale_factor: f32) {
+        self.scale_factor = valid_scale_factor(scale_factor);
+    }
+
+    #[inline(always)]
+    pub fn with_pixel_snapping(mut self, pixel_snapping: PixelSnapping) -> Self {
+        self.pixel_snapping = pixel_snapping;
+        self
+    }
+
+    #[inline(always)]
+    pub fn set_pixel_snapping(&mut self, pixel_snapping: PixelSnapping) {
+        self.pixel_snapping = pixel_snapping;
+    }
 }
 
 impl traits::TaffyConfig for TaffyConfig {
@@ -42,8 +120,76 @@ impl traits::TaffyConfig for TaffyConfig {
     fn use_rounding(&self) -> bool {
         self.use_rounding
     }
+
+    #[inline(always)]
+    fn display_fallback(&self) -> DisplayFallback {
+        self.display_fallback
+    }
+
+    #[inline(always)]
+    fn scale_factor(&self) -> f32 {
+        self.scale_factor
+    }
+
+    #[inline(always)]
+    fn pixel_snapping(&self) -> PixelSnapping {
+        self.pixel_snapping
+    }
+}
+
+/// The states a node publishes the results of a layout pass to.
+pub struct LayoutStates<T>
+where
+    T: 'static,
+{
+    /// Set to the size of the node when it changes
+    pub size: Option<State<Size<f32>, T>>,
+    /// Set to the space available to the node when it changes
+    pub constraints: Option<State<Size<AvailableSpace>, T>>,
+    /// Set to the scroll offset of the node when it changes
+    pub scroll_offset: Option<State<Point<f32>, T>>,
+}
+
+impl<T> Default for LayoutStates<T>
+where
+    T: 'static,
+{
+    fn default() -> Self {
+        Self {
+            size: None,
+            constraints: None,
+            scroll_offset: None,
+        }
+    }
+}
+
+impl<T> Clone for LayoutStates<T>
+where
+    T: 'static,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for LayoutStates<T> where T: 'static {}
+
+impl<T> Debug for LayoutStates<T>
+where
+    T: 'static,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LayoutStates")
+            .field("size", &self.size)
+            .field("constraints", &self.constraints)
+            .field("scroll_offset", &self.scroll_offset)
+            .finish()
+    }
 }
 
+/// Shared between a node and its content, which creates the states after the node.
+pub type LayoutStatesSlot<T> = Rc<Cell<LayoutStates<T>>>;
+
 #[derive(Debug, Clone)]
 pub struct LayoutNode<T>
 where
@@ -54,6 +200,29 @@ where
     pub final_layout: Layout,
     pub cache: Cache,
     pub context: Option<T>,
+    /// How far the content of the node is scrolled, children are shifted by the
+    /// negated offset when computing absolute positions.
+    pub scroll_offset: Point<f32>,
+    /// Set when the node changed since the last layout pass, the caches of its
+    /// ancestors are cleared before the next layout is computed.
+    pub dirty: bool,
+    /// The children laid out at the last layout pass, a difference means children were
+    /// inserted, removed or reordered since. Overlays are laid out separately.
+    pub layout_children: Vec<NodeKey>,
+    /// The available space of the last layout pass rooted at this node.
+    pub root_available_space: Option<Size<AvailableSpace>>,
+    /// Laid out as a separate layout root against the space available to the root,
+    /// see `widgets::overlay`.
+    pub overlay: bool,
+    /// The states the node publishes its layout to, see `widgets::sized`.
+    pub layout_states: Option<LayoutStatesSlot<T>>,
+    /// Animates the node from its previous layout whenever a layout pass changes it.
+    pub transition: Option<LayoutTransition>,
+    /// The running transition, advanced by `TaffyLayout::tick`.
+    pub animation: Option<LayoutAnimation>,
+    /// The resolved tracks of the last grid layout of the node.
+    #[cfg(feature = "detailed_layout_info")]
+    pub detailed_layout_info: DetailedLayoutInfo,
 }
 
 impl<T> LayoutNode<T>
@@ -68,6 +237,16 @@ where
             final_layout: Layout::new(),
             cache: Cache::new(),
             context: None,
+            scroll_offset: Point::ZERO,
+            dirty: true,
+            layout_children: Vec::new(),
+            root_available_space: None,
+            overlay: false,
+            layout_states: None,
+            transition: None,
+            animation: None,
+            #[cfg(feature = "detailed_layout_info")]
+            detailed_layout_info: DetailedLayoutInfo::None,
         }
     }
 
@@ -79,12 +258,38 @@ where
             final_layout: Layout::new(),
             cache: Cache::new(),
             context: Some(context),
+            scroll_offset: Point::ZERO,
+            dirty: true,
+            layout_children: Vec::new(),
+            root_available_space: None,
+            overlay: false,
+            layout_states: None,
+            transition: None,
+            animation: None,
+            #[cfg(feature = "detailed_layout_info")]
+            detailed_layout_info: DetailedLayoutInfo::None,
         }
     }
 
+    #[inline(always)]
+    pub fn with_transition(mut self, transition: LayoutTransition) -> Self {
+        self.transition = Some(transition);
+        self
+    }
+
     #[inline(always)]
     pub fn mark_dirty(&mut self) {
         self.cache.clear();
+        self.dirty = true;
+    }
+
+    /// Replaces the style and marks the node dirty if it changed.
+    #[inline(always)]
+    pub fn set_style(&mut self, style: Style) {
+        if self.style != style {
+            self.style = style;
+            self.mark_dirty();
+        }
     }
 }
 
@@ -181,6 +386,16 @@ where
         self.unrounded_layout = *layout;
     }
 
+    #[inline(always)]
+    fn get_scroll_offset(&self) -> Point<f32> {
+        self.scroll_offset
+    }
+
+    #[inline(always)]
+    fn set_scroll_offset(&mut self, offset: Point<f32>) {
+        self.scroll_offset = offset;
+    }
+
     #[inline(always)]
     fn cache_get(
         &self,
@@ -205,7 +420,91 @@ where
 
     #[inline(always)]
     fn cache_clear(&mut self) {
-        self.cache.clear()
+        self.cache.clear();
+    }
+
+    #[inline(always)]
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    #[inline(always)]
+    fn set_dirty(&mut self, dirty: bool) {
+        self.dirty = dirty;
+    }
+
+    #[inline(always)]
+    fn get_layout_children(&self) -> &[NodeKey] {
+        &self.layout_children
+    }
+
+    #[inline(always)]
+    fn set_layout_children(&mut self, children: &[NodeKey]) {
+        self.layout_children.clear();
+        self.layout_children.extend_from_slice(children);
+    }
+
+    #[inline(always)]
+    fn get_root_available_space(&self) -> Option<Size<AvailableSpace>> {
+        self.root_available_space
+    }
+
+    #[inline(always)]
+    fn set_root_available_space(&mut self, available_space: Option<Size<AvailableSpace>>) {
+        self.root_available_space = available_space;
+    }
+
+    #[inline(always)]
+    fn is_overlay(&self) -> bool {
+        self.overlay
+    }
+
+    #[inline(always)]
+    fn get_transition(&self) -> Option<LayoutTransition> {
+        self.transition
+    }
+
+    #[inline(always)]
+    fn get_animation(&self) -> Option<&LayoutAnimation> {
+        self.animation.as_ref()
+    }
+
+    #[inline(always)]
+    fn set_animation(&mut self, animation: Option<LayoutAnimation>) {
+        self.animation = animation;
+    }
+
+    #[inline(always)]
+    fn get_size_state(&self) -> Option<compose_rt::State<Size<f32>, Self>> {
+        self.layout_states
+            .as_ref()
+            .and_then(|states| states.get().size)
+    }
+
+    #[inline(always)]
+    fn get_constraints_state(&self) -> Option<compose_rt::State<Size<AvailableSpace>, Self>> {
+        self.layout_states
+            .as_ref()
+            .and_then(|states| states.get().constraints)
+    }
+
+    #[inline(always)]
+    fn get_scroll_offset_state(&self) -> Option<compose_rt::State<Point<f32>, Self>> {
+        self.layout_states
+            .as_ref()
+            .and_then(|states| states.get().scroll_offset)
+    }
+
+    #[cfg(feature = "detailed_layout_info")]
+    #[inline(always)]
+    fn get_detailed_layout_info(&self) -> &DetailedLayoutInfo {
+        &self.detailed_layout_info
+    }
+
+    #[cfg(feature = "detailed_layout_info")]
+    #[inline(always)]
+    fn set_detailed_layout_info(&mut self, info: DetailedLayoutInfo) {
+        self.detailed_layout_info = info;
     }
 
     #[inline(always)]
@@ -1,24 +1,139 @@
-use compose_rt::{NodeKey, Recomposer};
-use taffy::{compute_root_layout, print_tree, round_layout, AvailableSpace, NodeId, Size};
+use std::collections::HashSet;
+use std::{fmt, io};
 
-use crate::traits::{IntoNodeId, TaffyConfig, TaffyNode};
-use crate::{TaffyTree, TaffyTreeView};
+use compose_rt::{Composer, NodeKey, Recomposer};
+use taffy::style_helpers::TaffyMaxContent;
+#[cfg(feature = "detailed_layout_info")]
+use taffy::DetailedLayoutInfo;
+use taffy::{
+    compute_root_layout, print_tree, AvailableSpace, CoreStyle, Display, Layout, LayoutInput,
+    LayoutPartialTree, Line, NodeId, Overflow, Point, RequestedAxis, ResolveOrZero, RunMode, Size,
+    SizingMode,
+};
+
+use crate::animation::{advance_animations, start_transitions};
+use crate::changes::{layout_changes, publish, StateUpdates};
+use crate::geometry::{
+    absolute_layout, absolute_layouts, layout_children, layout_parent, layout_roots, node_data,
+    node_layout,
+};
+use crate::hit_test::hit_test_path;
+use crate::print::format_tree;
+use crate::rounding::snap_layout;
+use crate::scroll::{clamp_scroll_offsets, scroll_offset_states, scroll_to, set_scroll_offset};
+use crate::traits::{measure_node_context, IntoNodeId, Measure, TaffyConfig, TaffyNode};
+use crate::{AbsoluteLayout, LayoutChange, LayoutSnapshot, TaffyTree, TaffyTreeView};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum LayoutError {
     /// The supplied node was not found in the composer instance.
     InvalidInputNode(NodeKey),
+    /// The node has no layout data, e.g. a scope which did not create a node.
+    MissingNodeData(NodeKey),
+    /// The node is referenced as a child but is missing from the composer or has a different parent.
+    DetachedNode(NodeKey),
+    /// A container uses a display mode whose layout algorithm is disabled by cargo features.
+    DisabledAlgorithm(Display),
+    /// Writing the layout tree to the output failed.
+    WriteFailed(io::ErrorKind),
+}
+
+impl fmt::Display for LayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LayoutError::InvalidInputNode(node_key) => {
+                write!(f, "node {node_key} was not found in the composer")
+            }
+            LayoutError::MissingNodeData(node_key) => {
+                write!(f, "node {node_key} has no layout data")
+            }
+            LayoutError::DetachedNode(node_key) => {
+                write!(f, "node {node_key} is detached from its parent")
+            }
+            LayoutError::DisabledAlgorithm(display) => {
+                write!(f, "layout algorithm for {display:?} is disabled")
+            }
+            LayoutError::WriteFailed(kind) => write!(f, "failed to write layout tree: {kind}"),
+        }
+    }
+}
+
+impl std::error::Error for LayoutError {}
+
+/// How containers are laid out when their display mode's layout algorithm is disabled by cargo features.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum DisplayFallback {
+    /// Fail with `LayoutError::DisabledAlgorithm`.
+    #[default]
+    Error,
+    /// Lay out the container with the block algorithm, if it is enabled.
+    Block,
+    /// Lay out the container with the flexbox algorithm, if it is enabled.
+    Flex,
+    /// Hide the container and its children as with `Display::None`.
+    Hidden,
 }
 
-pub type LayoutResult = std::result::Result<(), LayoutError>;
+pub type LayoutResult<T = ()> = std::result::Result<T, LayoutError>;
 
 pub trait TaffyLayout<NodeContext, CoreContainerStyle> {
+    /// Computes the layout of the subtree rooted at the node, followed by the overlays
+    /// inside it which are laid out as separate roots against the same available space.
+    ///
+    /// Only the nodes of the subtree created, updated, or whose children were inserted,
+    /// removed or reordered since the last pass are invalidated together with their
+    /// ancestors, changes outside the subtree are left to the pass which includes them.
+    /// The pass is skipped when nothing changed and the subtree was last laid out with
+    /// the same available space. Changes to the config through `with_context_mut` are
+    /// not tracked, call `mark_dirty` on the root afterwards.
+    ///
+    /// Returns the nodes whose final layout differs from the previous pass.
     fn compute_layout_with<MeasureFn>(
         &mut self,
         available_space: Size<AvailableSpace>,
         node_key: NodeKey,
         measure_fn: MeasureFn,
-    ) -> LayoutResult
+    ) -> LayoutResult<Vec<LayoutChange>>
+    where
+        MeasureFn: FnMut(
+            Size<Option<f32>>,
+            Size<AvailableSpace>,
+            NodeId,
+            Option<&mut NodeContext>,
+            &CoreContainerStyle,
+        ) -> Size<f32>;
+
+    /// Computes the layout of the root node, leaves are zero sized. Use
+    /// `compute_layout_measured` to measure them or `compute_layout_with` to pass a measure
+    /// function.
+    fn compute_layout(
+        &mut self,
+        available_space: Size<AvailableSpace>,
+    ) -> LayoutResult<Vec<LayoutChange>>;
+
+    /// Computes the layout of the root node, measuring leaves with their node context's
+    /// [`Measure`] implementation.
+    fn compute_layout_measured(
+        &mut self,
+        available_space: Size<AvailableSpace>,
+    ) -> LayoutResult<Vec<LayoutChange>>
+    where
+        NodeContext: Measure;
+
+    /// Sizes the subtree rooted at the node to fit its content and computes its layout.
+    ///
+    /// The width is the max-content width of the node clamped between the min and max
+    /// sizes, the height is measured at that width and clamped likewise. The node is
+    /// laid out at the clamped size, e.g. the size of an auto-sized window, which is
+    /// returned together with the changed nodes. Overlays inside it use the clamped size
+    /// as their available space. Use `f32::INFINITY` for an unbounded max size.
+    fn compute_layout_to_fit_with<MeasureFn>(
+        &mut self,
+        min_size: Size<f32>,
+        max_size: Size<f32>,
+        node_key: NodeKey,
+        measure_fn: MeasureFn,
+    ) -> LayoutResult<(Size<f32>, Vec<LayoutChange>)>
     where
         MeasureFn: FnMut(
             Size<Option<f32>>,
@@ -28,11 +143,139 @@ pub trait TaffyLayout<NodeContext, CoreContainerStyle> {
             &CoreContainerStyle,
         ) -> Size<f32>;
 
-    fn compute_layout(&mut self, available_space: Size<AvailableSpace>) -> LayoutResult;
+    /// Sizes the root node to fit its content with zero sized leaves, see
+    /// `compute_layout_to_fit_with`.
+    fn compute_layout_to_fit(
+        &mut self,
+        min_size: Size<f32>,
+        max_size: Size<f32>,
+    ) -> LayoutResult<(Size<f32>, Vec<LayoutChange>)>;
+
+    /// Computes the size of the subtree rooted at the node for the available space without
+    /// committing a layout, e.g. its min-content or max-content size with
+    /// `Size::MIN_CONTENT` or `Size::MAX_CONTENT`.
+    ///
+    /// The stored layouts are left untouched, the layout caches of the nodes are reused
+    /// and filled by the pass.
+    fn compute_size_with<MeasureFn>(
+        &mut self,
+        available_space: Size<AvailableSpace>,
+        node_key: NodeKey,
+        measure_fn: MeasureFn,
+    ) -> LayoutResult<Size<f32>>
+    where
+        MeasureFn: FnMut(
+            Size<Option<f32>>,
+            Size<AvailableSpace>,
+            NodeId,
+            Option<&mut NodeContext>,
+            &CoreContainerStyle,
+        ) -> Size<f32>;
+
+    /// Computes the size of the root node with zero sized leaves, see `compute_size_with`.
+    fn compute_size(&mut self, available_space: Size<AvailableSpace>) -> LayoutResult<Size<f32>>;
+
+    /// Marks the node dirty, its layout cache and the caches of its ancestors are
+    /// cleared by the next layout pass.
+    fn mark_dirty(&mut self, node_key: NodeKey) -> LayoutResult;
+
+    /// Returns the final layout of the node, which is the rounded layout unless
+    /// rounding is disabled in the config.
+    fn layout(&self, node_key: NodeKey) -> LayoutResult<Layout>;
+
+    /// Returns the layout of the node before rounding was applied.
+    fn unrounded_layout(&self, node_key: NodeKey) -> LayoutResult<Layout>;
+
+    /// Returns the scroll offset of the node.
+    fn scroll_offset(&self, node_key: NodeKey) -> LayoutResult<Point<f32>>;
+
+    /// Returns the resolved row and column tracks of the last grid layout of the node,
+    /// `DetailedLayoutInfo::None` for nodes which were not laid out as a grid.
+    #[cfg(feature = "detailed_layout_info")]
+    fn detailed_layout_info(&self, node_key: NodeKey) -> LayoutResult<DetailedLayoutInfo>;
+
+    /// Scrolls the content of the node, the offset is clamped to the overflow of the
+    /// content along the axes with `Overflow::Hidden` or `Overflow::Scroll`.
+    /// Returns the offset which was applied.
+    fn set_scroll_offset(
+        &mut self,
+        node_key: NodeKey,
+        offset: Point<f32>,
+    ) -> LayoutResult<Point<f32>>;
+
+    /// Scrolls the content of the node relative to its current offset, see `set_scroll_offset`.
+    fn scroll_by(&mut self, node_key: NodeKey, delta: Point<f32>) -> LayoutResult<Point<f32>>;
+
+    /// Scrolls the ancestors of the node until its border box is in view.
+    fn scroll_to(&mut self, node_key: NodeKey) -> LayoutResult;
+
+    /// Returns the layout of the node interpolated by its running transition,
+    /// or the final layout when it is not animating.
+    fn animated_layout(&self, node_key: NodeKey) -> LayoutResult<Layout>;
+
+    /// Advances the running layout transitions by `dt`, returns the nodes whose
+    /// animated layout changed.
+    fn tick(&mut self, dt: f32) -> Vec<NodeKey>;
+
+    /// Returns the layout of the node positioned relative to its layout root,
+    /// taking the scroll offsets of its ancestors into account.
+    fn absolute_layout(&self, node_key: NodeKey) -> LayoutResult<AbsoluteLayout>;
+
+    /// Returns the absolute layouts of the subtree in pre-order, without the overlays
+    /// inside it.
+    fn absolute_layouts_with(
+        &self,
+        node_key: NodeKey,
+    ) -> LayoutResult<Vec<(NodeKey, AbsoluteLayout)>>;
+
+    /// Returns the absolute layouts of the root followed by those of the overlays in z-order.
+    fn absolute_layouts(&self) -> LayoutResult<Vec<(NodeKey, AbsoluteLayout)>>;
+
+    /// Returns the topmost node of the subtree containing the point.
+    ///
+    /// Later children are on top of earlier ones, subtrees with `Display::None` are skipped
+    /// and nodes with non-visible overflow clip their descendants to their padding box.
+    /// Overlays inside the subtree are not visited.
+    fn hit_test_with(&self, node_key: NodeKey, point: Point<f32>) -> LayoutResult<Option<NodeKey>>;
+
+    /// Returns the topmost node containing the point, visiting the overlays from the
+    /// topmost one before the root.
+    fn hit_test(&self, point: Point<f32>) -> LayoutResult<Option<NodeKey>>;
+
+    /// Returns the path from `node_key` down to the topmost node containing the point,
+    /// or an empty path if no node was hit.
+    fn hit_test_path_with(
+        &self,
+        node_key: NodeKey,
+        point: Point<f32>,
+    ) -> LayoutResult<Vec<NodeKey>>;
+
+    /// Returns the path from the root or the overlay which was hit down to the topmost
+    /// node containing the point, see `hit_test`.
+    fn hit_test_path(&self, point: Point<f32>) -> LayoutResult<Vec<NodeKey>>;
 
     fn print_layout_tree_with(&self, node_key: NodeKey) -> LayoutResult;
 
     fn print_layout_tree(&self) -> LayoutResult;
+
+    /// Formats the layout tree in the same format as `print_layout_tree_with`.
+    fn format_layout_tree_with(&self, node_key: NodeKey) -> LayoutResult<String>;
+
+    fn format_layout_tree(&self) -> LayoutResult<String>;
+
+    /// Writes the layout tree in the same format as `print_layout_tree_with`.
+    fn write_layout_tree_with<W>(&self, node_key: NodeKey, writer: &mut W) -> LayoutResult
+    where
+        W: io::Write;
+
+    fn write_layout_tree<W>(&self, writer: &mut W) -> LayoutResult
+    where
+        W: io::Write;
+
+    /// Captures the computed layout of the subtree for snapshot testing.
+    fn layout_snapshot_with(&self, node_key: NodeKey) -> LayoutResult<LayoutSnapshot>;
+
+    fn layout_snapshot(&self) -> LayoutResult<LayoutSnapshot>;
 }
 
 impl<S, T> TaffyLayout<T::NodeContext, T::CoreContainerStyle> for Recomposer<S, T>
@@ -46,7 +289,7 @@ where
         available_space: Size<AvailableSpace>,
         node_key: NodeKey,
         measure_function: MeasureFn,
-    ) -> LayoutResult
+    ) -> LayoutResult<Vec<LayoutChange>>
     where
         MeasureFn: FnMut(
             Size<Option<f32>>,
@@ -56,31 +299,246 @@ where
             &T::CoreContainerStyle,
         ) -> Size<f32>,
     {
+        self.with_composer_mut(|composer| {
+            prepare_layout(composer, node_key)?;
+            let mut tree = TaffyTree::new(composer, measure_function);
+            Ok(perform_layouts(&mut tree, node_key, available_space, None))
+        })
+        .map(LayoutPass::publish)
+    }
+
+    #[inline(always)]
+    fn compute_layout(
+        &mut self,
+        available_space: Size<AvailableSpace>,
+    ) -> LayoutResult<Vec<LayoutChange>> {
+        let node_key = self.root_node_key();
+        self.compute_layout_with(available_space, node_key, |_, _, _, _, _| Size::ZERO)
+    }
+
+    #[inline(always)]
+    fn compute_layout_measured(
+        &mut self,
+        available_space: Size<AvailableSpace>,
+    ) -> LayoutResult<Vec<LayoutChange>>
+    where
+        T::NodeContext: Measure,
+    {
+        let node_key = self.root_node_key();
+        self.compute_layout_with(available_space, node_key, measure_node_context)
+    }
+
+    fn compute_layout_to_fit_with<MeasureFn>(
+        &mut self,
+        min_size: Size<f32>,
+        max_size: Size<f32>,
+        node_key: NodeKey,
+        measure_function: MeasureFn,
+    ) -> LayoutResult<(Size<f32>, Vec<LayoutChange>)>
+    where
+        MeasureFn: FnMut(
+            Size<Option<f32>>,
+            Size<AvailableSpace>,
+            NodeId,
+            Option<&mut T::NodeContext>,
+            &T::CoreContainerStyle,
+        ) -> Size<f32>,
+    {
+        self.with_composer_mut(|composer| {
+            prepare_layout(composer, node_key)?;
+            let mut tree = TaffyTree::new(composer, measure_function);
+            let size = fit_to_content(&mut tree, node_key, min_size, max_size);
+            let available_space = size.map(AvailableSpace::Definite);
+            let pass = perform_layouts(&mut tree, node_key, available_space, Some(size));
+            LayoutResult::Ok((size, pass))
+        })
+        .map(|(size, pass)| (size, pass.publish()))
+    }
+
+    #[inline(always)]
+    fn compute_layout_to_fit(
+        &mut self,
+        min_size: Size<f32>,
+        max_size: Size<f32>,
+    ) -> LayoutResult<(Size<f32>, Vec<LayoutChange>)> {
+        let node_key = self.root_node_key();
+        self.compute_layout_to_fit_with(min_size, max_size, node_key, |_, _, _, _, _| Size::ZERO)
+    }
+
+    fn compute_size_with<MeasureFn>(
+        &mut self,
+        available_space: Size<AvailableSpace>,
+        node_key: NodeKey,
+        measure_function: MeasureFn,
+    ) -> LayoutResult<Size<f32>>
+    where
+        MeasureFn: FnMut(
+            Size<Option<f32>>,
+            Size<AvailableSpace>,
+            NodeId,
+            Option<&mut T::NodeContext>,
+            &T::CoreContainerStyle,
+        ) -> Size<f32>,
+    {
+        self.with_composer_mut(|composer| {
+            prepare_layout(composer, node_key)?;
+            let mut tree = TaffyTree::new(composer, measure_function);
+            Ok(measure_size(
+                &mut tree,
+                node_key,
+                Size::NONE,
+                available_space,
+            ))
+        })
+    }
+
+    #[inline(always)]
+    fn compute_size(&mut self, available_space: Size<AvailableSpace>) -> LayoutResult<Size<f32>> {
+        let node_key = self.root_node_key();
+        self.compute_size_with(available_space, node_key, |_, _, _, _, _| Size::ZERO)
+    }
+
+    fn mark_dirty(&mut self, node_key: NodeKey) -> LayoutResult {
         self.with_composer_mut(|composer| {
             if !composer.nodes.contains(node_key) {
                 return Err(LayoutError::InvalidInputNode(node_key));
             }
-            let node_id = node_key.into_node_id();
-            let mut tree = TaffyTree::new(composer, measure_function);
-            compute_root_layout(&mut tree, node_id, available_space);
-            if tree.composer.context.use_rounding() {
-                round_layout(&mut tree, node_id);
+            if let Some(data) = composer.nodes[node_key].data.as_mut() {
+                data.cache_clear();
+                data.set_dirty(true);
             }
             Ok(())
         })
     }
 
+    fn layout(&self, node_key: NodeKey) -> LayoutResult<Layout> {
+        self.with_composer(|composer| node_layout(composer, node_key).copied())
+    }
+
+    fn unrounded_layout(&self, node_key: NodeKey) -> LayoutResult<Layout> {
+        self.with_composer(|composer| {
+            let data = node_data(composer, node_key)?;
+            Ok(*data.get_unrounded_layout())
+        })
+    }
+
+    fn scroll_offset(&self, node_key: NodeKey) -> LayoutResult<Point<f32>> {
+        self.with_composer(|composer| Ok(node_data(composer, node_key)?.get_scroll_offset()))
+    }
+
+    #[cfg(feature = "detailed_layout_info")]
+    fn detailed_layout_info(&self, node_key: NodeKey) -> LayoutResult<DetailedLayoutInfo> {
+        self.with_composer(|composer| {
+            Ok(node_data(composer, node_key)?
+                .get_detailed_layout_info()
+                .clone())
+        })
+    }
+
+    fn set_scroll_offset(
+        &mut self,
+        node_key: NodeKey,
+        offset: Point<f32>,
+    ) -> LayoutResult<Point<f32>> {
+        let (offset, states) = self.with_composer_mut(|composer| {
+            let offset = set_scroll_offset(composer, node_key, offset)?;
+            LayoutResult::Ok((offset, scroll_offset_states(composer, &[node_key])))
+        })?;
+        publish(states);
+        Ok(offset)
+    }
+
+    fn scroll_by(&mut self, node_key: NodeKey, delta: Point<f32>) -> LayoutResult<Point<f32>> {
+        let offset = self.scroll_offset(node_key)?;
+        let offset = Point {
+            x: offset.x + delta.x,
+            y: offset.y + delta.y,
+        };
+        self.set_scroll_offset(node_key, offset)
+    }
+
+    fn scroll_to(&mut self, node_key: NodeKey) -> LayoutResult {
+        let states = self.with_composer_mut(|composer| {
+            let scrolled = scroll_to(composer, node_key)?;
+            LayoutResult::Ok(scroll_offset_states(composer, &scrolled))
+        })?;
+        publish(states);
+        Ok(())
+    }
+
+    fn animated_layout(&self, node_key: NodeKey) -> LayoutResult<Layout> {
+        self.with_composer(|composer| {
+            let data = node_data(composer, node_key)?;
+            match data.get_animation() {
+                Some(animation) => Ok(animation.layout()),
+                None => node_layout(composer, node_key).copied(),
+            }
+        })
+    }
+
+    fn tick(&mut self, dt: f32) -> Vec<NodeKey> {
+        self.with_composer_mut(|composer| advance_animations(composer, dt))
+    }
+
+    fn absolute_layout(&self, node_key: NodeKey) -> LayoutResult<AbsoluteLayout> {
+        self.with_composer(|composer| absolute_layout(composer, node_key))
+    }
+
+    fn absolute_layouts_with(
+        &self,
+        node_key: NodeKey,
+    ) -> LayoutResult<Vec<(NodeKey, AbsoluteLayout)>> {
+        self.with_composer(|composer| absolute_layouts(composer, node_key))
+    }
+
+    fn absolute_layouts(&self) -> LayoutResult<Vec<(NodeKey, AbsoluteLayout)>> {
+        let node_key = self.root_node_key();
+        self.with_composer(|composer| {
+            let mut layouts = Vec::new();
+            for root_key in layout_roots(composer, node_key) {
+                layouts.extend(absolute_layouts(composer, root_key)?);
+            }
+            Ok(layouts)
+        })
+    }
+
+    fn hit_test_with(&self, node_key: NodeKey, point: Point<f32>) -> LayoutResult<Option<NodeKey>> {
+        self.with_composer(|composer| {
+            let path = hit_test_path(composer, node_key, point)?;
+            Ok(path.last().copied())
+        })
+    }
+
     #[inline(always)]
-    fn compute_layout(&mut self, available_space: Size<AvailableSpace>) -> LayoutResult {
+    fn hit_test(&self, point: Point<f32>) -> LayoutResult<Option<NodeKey>> {
+        let path = self.hit_test_path(point)?;
+        Ok(path.last().copied())
+    }
+
+    fn hit_test_path_with(
+        &self,
+        node_key: NodeKey,
+        point: Point<f32>,
+    ) -> LayoutResult<Vec<NodeKey>> {
+        self.with_composer(|composer| hit_test_path(composer, node_key, point))
+    }
+
+    fn hit_test_path(&self, point: Point<f32>) -> LayoutResult<Vec<NodeKey>> {
         let node_key = self.root_node_key();
-        self.compute_layout_with(available_space, node_key, |_, _, _, _, _| Size::ZERO)
+        self.with_composer(|composer| {
+            for root_key in layout_roots(composer, node_key).into_iter().rev() {
+                let path = hit_test_path(composer, root_key, point)?;
+                if !path.is_empty() {
+                    return Ok(path);
+                }
+            }
+            Ok(Vec::new())
+        })
     }
 
     fn print_layout_tree_with(&self, node_key: NodeKey) -> LayoutResult {
         self.with_composer(|composer| {
-            if !composer.nodes.contains(node_key) {
-                return Err(LayoutError::InvalidInputNode(node_key));
-            }
+            validate_tree(composer, node_key)?;
             let node_id = node_key.into_node_id();
             let tree = TaffyTreeView::new(composer);
             print_tree(&tree, node_id);
@@ -93,4 +551,812 @@ where
         let node_key = self.root_node_key();
         self.print_layout_tree_with(node_key)
     }
+
+    fn format_layout_tree_with(&self, node_key: NodeKey) -> LayoutResult<String> {
+        self.with_composer(|composer| {
+            validate_tree(composer, node_key)?;
+            let node_id = node_key.into_node_id();
+            let tree = TaffyTreeView::new(composer);
+            Ok(format_tree(&tree, node_id))
+        })
+    }
+
+    #[inline(always)]
+    fn format_layout_tree(&self) -> LayoutResult<String> {
+        let node_key = self.root_node_key();
+        self.format_layout_tree_with(node_key)
+    }
+
+    fn write_layout_tree_with<W>(&self, node_key: NodeKey, writer: &mut W) -> LayoutResult
+    where
+        W: io::Write,
+    {
+        let output = self.format_layout_tree_with(node_key)?;
+        writer
+            .write_all(output.as_bytes())
+            .map_err(|err| LayoutError::WriteFailed(err.kind()))
+    }
+
+    #[inline(always)]
+    fn write_layout_tree<W>(&self, writer: &mut W) -> LayoutResult
+    where
+        W: io::Write,
+    {
+        let node_key = self.root_node_key();
+        self.write_layout_tree_with(node_key, writer)
+    }
+
+    fn layout_snapshot_with(&self, node_key: NodeKey) -> LayoutResult<LayoutSnapshot> {
+        self.with_composer(|composer| {
+            validate_tree(composer, node_key)?;
+            let tree = TaffyTreeView::new(composer);
+            Ok(LayoutSnapshot::new(&tree, node_key))
+        })
+    }
+
+    #[inline(always)]
+    fn layout_snapshot(&self) -> LayoutResult<LayoutSnapshot> {
+        let node_key = self.root_node_key();
+        self.layout_snapshot_with(node_key)
+    }
+}
+
+/// The outcome of a layout pass, holding the states to publish once the composer is
+/// released.
+pub(crate) struct LayoutPass<T>
+where
+    T: TaffyNode,
+{
+    changes: Vec<LayoutChange>,
+    sizes: StateUpdates<Size<f32>, T>,
+    constraints: StateUpdates<Size<AvailableSpace>, T>,
+    offsets: StateUpdates<Point<f32>, T>,
+}
+
+impl<T> Default for LayoutPass<T>
+where
+    T: TaffyNode,
+{
+    fn default() -> Self {
+        Self {
+            changes: Vec::new(),
+            sizes: Vec::new(),
+            constraints: Vec::new(),
+            offsets: Vec::new(),
+        }
+    }
+}
+
+impl<T> LayoutPass<T>
+where
+    T: TaffyNode,
+{
+    /// Adds the changes and states of another pass.
+    pub(crate) fn append(&mut self, mut other: Self) {
+        self.changes.append(&mut other.changes);
+        self.sizes.append(&mut other.sizes);
+        self.constraints.append(&mut other.constraints);
+        self.offsets.append(&mut other.offsets);
+    }
+
+    /// Publishes the states, returns the changed nodes.
+    pub(crate) fn publish(self) -> Vec<LayoutChange> {
+        publish(self.sizes);
+        publish(self.constraints);
+        publish(self.offsets);
+        self.changes
+    }
+}
+
+/// Validates the subtree and invalidates its changed nodes.
+pub(crate) fn prepare_layout<T>(composer: &mut Composer<T>, node_key: NodeKey) -> LayoutResult
+where
+    T: TaffyNode,
+    T::Context: TaffyConfig,
+{
+    validate_tree(composer, node_key)?;
+    invalidate_changed_nodes(composer, node_key);
+    Ok(())
+}
+
+/// Lays out the subtree rooted at the node followed by the overlays inside it, each
+/// against the available space, the node at `root_size` when given. A root is skipped
+/// when it was last laid out with the same available space and nothing inside it was
+/// invalidated since.
+pub(crate) fn perform_layouts<T, M>(
+    tree: &mut TaffyTree<'_, T, M>,
+    node_key: NodeKey,
+    available_space: Size<AvailableSpace>,
+    root_size: Option<Size<f32>>,
+) -> LayoutPass<T>
+where
+    T: TaffyNode,
+    T::Context: TaffyConfig,
+    M: FnMut(
+        Size<Option<f32>>,
+        Size<AvailableSpace>,
+        NodeId,
+        Option<&mut T::NodeContext>,
+        &T::CoreContainerStyle,
+    ) -> Size<f32>,
+{
+    let mut pass = LayoutPass::default();
+    for root_key in layout_roots(tree.composer, node_key) {
+        let root = tree.composer.nodes[root_key].data.as_ref();
+        let laid_out = root.and_then(|root| root.get_root_available_space());
+        let size = root_size.filter(|_| root_key == node_key);
+        if size.is_some() || laid_out != Some(available_space) {
+            pass.append(perform_layout(tree, root_key, available_space, size));
+        }
+    }
+    pass
+}
+
+/// Computes the size of the node without laying out its subtree.
+pub(crate) fn measure_size<T, M>(
+    tree: &mut TaffyTree<'_, T, M>,
+    node_key: NodeKey,
+    known_dimensions: Size<Option<f32>>,
+    available_space: Size<AvailableSpace>,
+) -> Size<f32>
+where
+    T: TaffyNode,
+    T::Context: TaffyConfig,
+    M: FnMut(
+        Size<Option<f32>>,
+        Size<AvailableSpace>,
+        NodeId,
+        Option<&mut T::NodeContext>,
+        &T::CoreContainerStyle,
+    ) -> Size<f32>,
+{
+    let inputs = LayoutInput {
+        known_dimensions,
+        parent_size: available_space.into_options(),
+        available_space,
+        sizing_mode: SizingMode::InherentSize,
+        axis: RequestedAxis::Both,
+        run_mode: RunMode::ComputeSize,
+        vertical_margins_are_collapsible: Line::FALSE,
+    };
+    tree.compute_child_layout(node_key.into_node_id(), inputs)
+        .size
+}
+
+/// Returns the max-content width of the node and its height at that width, clamped
+/// between the min and max sizes.
+fn fit_to_content<T, M>(
+    tree: &mut TaffyTree<'_, T, M>,
+    node_key: NodeKey,
+    min_size: Size<f32>,
+    max_size: Size<f32>,
+) -> Size<f32>
+where
+    T: TaffyNode,
+    T::Context: TaffyConfig,
+    M: FnMut(
+        Size<Option<f32>>,
+        Size<AvailableSpace>,
+        NodeId,
+        Option<&mut T::NodeContext>,
+        &T::CoreContainerStyle,
+    ) -> Size<f32>,
+{
+    let clamp = |value: f32, min: f32, max: f32| value.min(max).max(min);
+    let max_content = measure_size(tree, node_key, Size::NONE, Size::MAX_CONTENT);
+    let width = clamp(max_content.width, min_size.width, max_size.width);
+    let known_dimensions = Size {
+        width: Some(width),
+        height: None,
+    };
+    let available_space = Size {
+        width: AvailableSpace::Definite(width),
+        height: AvailableSpace::MaxContent,
+    };
+    let height = measure_size(tree, node_key, known_dimensions, available_space).height;
+    Size {
+        width,
+        height: clamp(height, min_size.height, max_size.height),
+    }
+}
+
+/// Lays out the subtree rooted at the node, at `size` when given, and collects the
+/// changes of the pass.
+fn perform_layout<T, M>(
+    tree: &mut TaffyTree<'_, T, M>,
+    node_key: NodeKey,
+    available_space: Size<AvailableSpace>,
+    size: Option<Size<f32>>,
+) -> LayoutPass<T>
+where
+    T: TaffyNode,
+    T::Context: TaffyConfig,
+    M: FnMut(
+        Size<Option<f32>>,
+        Size<AvailableSpace>,
+        NodeId,
+        Option<&mut T::NodeContext>,
+        &T::CoreContainerStyle,
+    ) -> Size<f32>,
+{
+    let node_id = node_key.into_node_id();
+    match size {
+        Some(size) => compute_sized_root_layout(tree, node_id, size),
+        None => compute_root_layout(tree, node_id, available_space),
+    }
+    let config = &tree.composer.context;
+    if config.use_rounding() {
+        let (scale_factor, snapping) = (config.scale_factor(), config.pixel_snapping());
+        snap_layout(tree, node_id, scale_factor, snapping);
+    }
+    let previous_layouts = std::mem::take(&mut tree.previous_layouts);
+    let constraints = std::mem::take(&mut tree.constraints);
+    let composer = &mut *tree.composer;
+    if let Some(root) = composer.nodes[node_key].data.as_mut() {
+        // a sized pass does not lay the node out as the available space alone would
+        root.set_root_available_space(size.is_none().then_some(available_space));
+    }
+    if let Some(parent_key) = layout_parent(composer, node_key) {
+        // the pass moved the node, the enclosing layout must place it again
+        invalidate_ancestors(composer, parent_key, &mut HashSet::new());
+    }
+    let changes = layout_changes(composer, previous_layouts);
+    start_transitions(composer, &changes);
+    let changed_keys = changes
+        .iter()
+        .map(|change| change.node_key)
+        .collect::<Vec<_>>();
+    clamp_scroll_offsets(composer, &changed_keys);
+    let sizes = changes
+        .iter()
+        .filter(|change| change.resized())
+        .filter_map(|change| {
+            let data = composer.nodes[change.node_key].data.as_ref()?;
+            Some((data.get_size_state()?, change.current.size))
+        })
+        .collect();
+    let offsets = scroll_offset_states(composer, &changed_keys);
+    LayoutPass {
+        changes,
+        sizes,
+        constraints,
+        offsets,
+    }
+}
+
+/// Like `taffy::compute_root_layout`, but lays the root out at the given size instead of
+/// the size resolved from its style.
+fn compute_sized_root_layout(tree: &mut impl LayoutPartialTree, node_id: NodeId, size: Size<f32>) {
+    let available_space = size.map(AvailableSpace::Definite);
+    let inputs = LayoutInput {
+        known_dimensions: size.map(Some),
+        parent_size: available_space.into_options(),
+        available_space,
+        sizing_mode: SizingMode::InherentSize,
+        axis: RequestedAxis::Both,
+        run_mode: RunMode::PerformLayout,
+        vertical_margins_are_collapsible: Line::FALSE,
+    };
+    let output = tree.compute_child_layout(node_id, inputs);
+    let style = tree.get_core_container_style(node_id);
+    let scrollbar_width = |overflow: Overflow| match overflow {
+        Overflow::Scroll => style.scrollbar_width(),
+        _ => 0.0,
+    };
+    let layout = Layout {
+        order: 0,
+        location: Point::ZERO,
+        size: output.size,
+        #[cfg(feature = "content_size")]
+        content_size: output.content_size,
+        scrollbar_size: Size {
+            width: scrollbar_width(style.overflow().y),
+            height: scrollbar_width(style.overflow().x),
+        },
+        padding: style.padding().resolve_or_zero(Some(size.width)),
+        border: style.border().resolve_or_zero(Some(size.width)),
+        margin: style.margin().resolve_or_zero(Some(size.width)),
+    };
+    drop(style);
+    tree.set_unrounded_layout(node_id, &layout);
+}
+
+/// Checks that every node of the subtree has layout data, is linked to its parent
+/// and uses an enabled layout algorithm, so the tree adapters can not panic.
+fn validate_tree<T>(composer: &Composer<T>, node_key: NodeKey) -> LayoutResult
+where
+    T: TaffyNode,
+    T::Context: TaffyConfig,
+{
+    let fallback = composer.context.display_fallback();
+    if !composer.nodes.contains(node_key) {
+        return Err(LayoutError::InvalidInputNode(node_key));
+    }
+    let mut stack = vec![node_key];
+    while let Some(node_key) = stack.pop() {
+        let node = &composer.nodes[node_key];
+        let data = node
+            .data
+            .as_ref()
+            .ok_or(LayoutError::MissingNodeData(node_key))?;
+        let display = data.get_display();
+        if !node.children.is_empty() && resolve_display(display, fallback).is_none() {
+            return Err(LayoutError::DisabledAlgorithm(display));
+        }
+        for &child_key in &node.children {
+            match composer.nodes.get(child_key) {
+                Some(child) if child.parent == node_key => stack.push(child_key),
+                _ => return Err(LayoutError::DetachedNode(child_key)),
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Returns the display mode used to lay out a node, applying the fallback when
+/// the algorithm of its own display mode is disabled.
+// taffy may have more algorithms enabled than this crate through feature unification
+#[allow(unreachable_patterns)]
+pub(crate) fn resolve_display(display: Display, fallback: DisplayFallback) -> Option<Display> {
+    match display {
+        Display::None => return Some(Display::None),
+        #[cfg(feature = "block_layout")]
+        Display::Block => return Some(Display::Block),
+        #[cfg(feature = "flexbox")]
+        Display::Flex => return Some(Display::Flex),
+        #[cfg(feature = "grid")]
+        Display::Grid => return Some(Display::Grid),
+        _ => {}
+    }
+    match fallback {
+        DisplayFallback::Error => None,
+        #[cfg(feature = "block_layout")]
+        DisplayFallback::Block => Some(Display::Block),
+        #[cfg(feature = "flexbox")]
+        DisplayFallback::Flex => Some(Display::Flex),
+        DisplayFallback::Hidden => Some(Display::None),
+        _ => None,
+    }
+}
+
+/// Clears the caches of the nodes of the subtree marked dirty or whose children changed
+/// since the last layout pass together with the caches of their ancestors.
+///
+/// compose-rt keeps the nodes created, updated and removed by a recomposition private,
+/// so changes are detected here instead: styles mark their node dirty when they are set,
+/// and insertions, removals and reorders show up as a difference between the children
+/// of a node and the children it was last laid out with.
+fn invalidate_changed_nodes<T>(composer: &mut Composer<T>, node_key: NodeKey)
+where
+    T: TaffyNode,
+{
+    let mut changed_nodes = Vec::new();
+    let mut children = Vec::new();
+    let mut stack = vec![node_key];
+    while let Some(node_key) = stack.pop() {
+        let node = &composer.nodes[node_key];
+        stack.extend_from_slice(&node.children);
+        let Some(data) = node.data.as_ref() else {
+            continue;
+        };
+        children.clear();
+        children.extend(layout_children(composer, node_key));
+        if data.get_layout_children() != children.as_slice() {
+            if let Some(data) = composer.nodes[node_key].data.as_mut() {
+                data.set_layout_children(&children);
+            }
+            changed_nodes.push(node_key);
+        } else if data.is_dirty() {
+            changed_nodes.push(node_key);
+        }
+    }
+    let mut visited = HashSet::with_capacity(changed_nodes.len());
+    for &node_key in &changed_nodes {
+        invalidate_ancestors(composer, node_key, &mut visited);
+    }
+}
+
+/// Walks up the parent links from `node_key` clearing the caches and forgetting the
+/// available space of the last pass rooted at each node, so no enclosing layout root
+/// skips its next pass. Stops at a layout root or at a node that was already
+/// invalidated in the same pass.
+fn invalidate_ancestors<T>(
+    composer: &mut Composer<T>,
+    node_key: NodeKey,
+    visited: &mut HashSet<NodeKey>,
+) where
+    T: TaffyNode,
+{
+    let mut current = node_key;
+    while visited.insert(current) {
+        let Some(node) = composer.nodes.get_mut(current) else {
+            break;
+        };
+        let Some(data) = node.data.as_mut() else {
+            break;
+        };
+        data.cache_clear();
+        data.set_dirty(false);
+        data.set_root_available_space(None);
+        if node.parent == current || data.is_overlay() {
+            break;
+        }
+        current = node.parent;
+    }
+}
+
+#[cfg(all(test, feature = "flexbox"))]
+mod tests {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    use compose_rt::{Composer, Recomposer, Root};
+    use taffy::Style;
+
+    use super::*;
+    use crate::impls::{LayoutNode, Scope, State, TaffyConfig};
+    use crate::modifier::{modifier, StyleModifier};
+    use crate::test_utils::{children, point, SPACE};
+    use crate::widgets::{column, leaf};
+
+    /// A column of two leaves, the first one 20 high and the second one 50 high, which
+    /// are swapped when the root state is set.
+    fn app(s: Scope<Root, ()>, swapped: State<bool, ()>) {
+        column(s, modifier().size(100.0, 100.0), move |s| {
+            let first = |s: Scope<_, ()>| leaf(s, modifier().height(20.0));
+            let second = |s: Scope<_, ()>| leaf(s, modifier().height(50.0));
+            if swapped.get() {
+                s.key(2, second);
+                s.key(1, first);
+            } else {
+                s.key(1, first);
+                s.key(2, second);
+            }
+        });
+    }
+
+    fn compose() -> Recomposer<bool, LayoutNode<()>> {
+        Composer::compose_with(app, TaffyConfig::default(), || false)
+    }
+
+    fn location(recomposer: &Recomposer<bool, LayoutNode<()>>, node_key: NodeKey) -> Point<f32> {
+        recomposer.layout(node_key).unwrap().location
+    }
+
+    #[test]
+    fn subtree_pass_does_not_skip_the_next_full_pass() {
+        let mut recomposer = compose();
+        recomposer.compute_layout(SPACE).unwrap();
+        let child = children(&recomposer, recomposer.root_node_key())[1];
+        assert_eq!(location(&recomposer, child), point(0.0, 20.0));
+
+        recomposer
+            .compute_layout_with(SPACE, child, |_, _, _, _, _| Size::ZERO)
+            .unwrap();
+        assert_eq!(location(&recomposer, child), Point::ZERO);
+
+        let changes = recomposer.compute_layout(SPACE).unwrap();
+        assert_eq!(location(&recomposer, child), point(0.0, 20.0));
+        assert!(changes.iter().any(|change| change.node_key == child));
+    }
+
+    #[test]
+    fn subtree_pass_leaves_dirty_siblings_for_the_next_full_pass() {
+        let mut recomposer = compose();
+        recomposer.compute_layout(SPACE).unwrap();
+        let [sibling, child] = children(&recomposer, recomposer.root_node_key())[..] else {
+            panic!("expected two children");
+        };
+        recomposer.with_composer_mut(|composer| {
+            let data = composer.nodes[sibling].data.as_mut().unwrap();
+            data.set_style(modifier().height(30.0));
+        });
+
+        recomposer
+            .compute_layout_with(SPACE, child, |_, _, _, _, _| Size::ZERO)
+            .unwrap();
+        recomposer.compute_layout(SPACE).unwrap();
+        assert_eq!(location(&recomposer, child), point(0.0, 30.0));
+    }
+
+    #[test]
+    fn unchanged_tree_is_not_laid_out_again() {
+        let mut recomposer = compose();
+        recomposer.compute_layout(SPACE).unwrap();
+        // without caches any pass which is not skipped measures the leaves again
+        recomposer.with_composer_mut(|composer| {
+            for (_, node) in composer.nodes.iter_mut() {
+                if let Some(data) = node.data.as_mut() {
+                    data.cache_clear();
+                }
+            }
+        });
+        let measured = Rc::new(Cell::new(0));
+        let measure = {
+            let measured = measured.clone();
+            move |_: Size<Option<f32>>,
+                  _: Size<AvailableSpace>,
+                  _: NodeId,
+                  _: Option<&mut ()>,
+                  _: &Style| {
+                measured.set(measured.get() + 1);
+                Size::ZERO
+            }
+        };
+        let root = recomposer.root_node_key();
+        let changes = recomposer
+            .compute_layout_with(SPACE, root, measure.clone())
+            .unwrap();
+        assert!(changes.is_empty());
+        assert_eq!(measured.get(), 0);
+
+        let changes = recomposer
+            .compute_layout_with(SPACE.map(|_| AvailableSpace::MaxContent), root, measure)
+            .unwrap();
+        assert!(changes.is_empty());
+        assert!(measured.get() > 0);
+    }
+
+    #[test]
+    fn fitted_size_is_the_size_of_the_root() {
+        fn app(s: Scope<Root, ()>) {
+            column(s, Style::default(), |s| {
+                leaf(s, modifier().size(50.0, 20.0));
+            });
+        }
+        let size = |width, height| Size { width, height };
+        let mut recomposer = Composer::compose(app, TaffyConfig::default());
+        let root = recomposer.root_node_key();
+        let fit = |recomposer: &mut Recomposer<(), LayoutNode<()>>, min: f32, max: f32| {
+            let (fitted, _) = recomposer
+                .compute_layout_to_fit(size(min, min), size(max, max))
+                .unwrap();
+            assert_eq!(recomposer.layout(root).unwrap().size, fitted);
+            fitted
+        };
+        assert_eq!(fit(&mut recomposer, 100.0, 300.0), size(100.0, 100.0));
+        assert_eq!(fit(&mut recomposer, 0.0, 300.0), size(50.0, 20.0));
+        assert_eq!(fit(&mut recomposer, 0.0, 10.0), size(10.0, 10.0));
+    }
+
+    #[test]
+    fn computed_sizes_leave_the_layouts_untouched() {
+        fn app(s: Scope<Root, ()>) {
+            column(s, Style::default(), |s| {
+                leaf(s, modifier().size(50.0, 20.0));
+                leaf(s, modifier().flex_grow(1.0));
+            });
+        }
+        let mut recomposer = Composer::compose(app, TaffyConfig::default());
+        recomposer.compute_layout(SPACE).unwrap();
+        let snapshot = recomposer.layout_snapshot().unwrap();
+
+        let min_content = recomposer
+            .compute_size(SPACE.map(|_| AvailableSpace::MinContent))
+            .unwrap();
+        let max_content = recomposer
+            .compute_size(SPACE.map(|_| AvailableSpace::MaxContent))
+            .unwrap();
+        assert_eq!(
+            min_content,
+            Size {
+                width: 50.0,
+                height: 20.0
+            }
+        );
+        assert_eq!(
+            max_content,
+            Size {
+                width: 50.0,
+                height: 20.0
+            }
+        );
+        assert_eq!(recomposer.layout_snapshot().unwrap(), snapshot);
+        assert!(recomposer.compute_layout(SPACE).unwrap().is_empty());
+    }
+
+    #[test]
+    fn removed_children_are_not_visited_before_the_next_layout() {
+        fn app(s: Scope<Root, ()>, count: State<usize, ()>) {
+            column(s, modifier().size(100.0, 100.0), move |s| {
+                for index in 0..count.get() {
+                    s.key(index, |s| leaf(s, modifier().height(20.0)));
+                }
+            });
+        }
+        let mut recomposer = Composer::compose_with(app, TaffyConfig::default(), || 3);
+        recomposer.compute_layout(SPACE).unwrap();
+        recomposer.recompose_with(1);
+        let root = recomposer.root_node_key();
+        let remaining = recomposer.with_composer(|composer| composer.nodes[root].children[0]);
+
+        let tree = recomposer.format_layout_tree().unwrap();
+        assert_eq!(tree.matches("LEAF").count(), 1);
+        let layouts = recomposer.absolute_layouts().unwrap();
+        assert_eq!(layouts.len(), 2);
+        assert_eq!(layouts[1].0, remaining);
+        assert_eq!(
+            recomposer.hit_test(point(10.0, 10.0)).unwrap(),
+            Some(remaining)
+        );
+    }
+
+    #[test]
+    fn reordered_children_are_laid_out_again() {
+        let mut recomposer = compose();
+        recomposer.compute_layout(SPACE).unwrap();
+        let second = children(&recomposer, recomposer.root_node_key())[1];
+        assert_eq!(location(&recomposer, second), point(0.0, 20.0));
+
+        recomposer.recompose_with(true);
+        let [second, first] = children(&recomposer, recomposer.root_node_key())[..] else {
+            panic!("expected two children");
+        };
+        recomposer.compute_layout(SPACE).unwrap();
+        assert_eq!(location(&recomposer, second), Point::ZERO);
+        assert_eq!(location(&recomposer, first), point(0.0, 50.0));
+    }
+
+    #[test]
+    fn invalid_trees_are_rejected() {
+        let mut recomposer = compose();
+        let child = children(&recomposer, recomposer.root_node_key())[1];
+        let result = recomposer.compute_layout_with(SPACE, usize::MAX, |_, _, _, _, _| Size::ZERO);
+        assert_eq!(result, Err(LayoutError::InvalidInputNode(usize::MAX)));
+
+        recomposer.with_composer_mut(|composer| composer.nodes[child].parent = child);
+        let result = recomposer.compute_layout(SPACE);
+        assert_eq!(result, Err(LayoutError::DetachedNode(child)));
+
+        let mut recomposer = compose();
+        recomposer.with_composer_mut(|composer| composer.nodes[child].data = None);
+        let result = recomposer.compute_layout(SPACE);
+        assert_eq!(result, Err(LayoutError::MissingNodeData(child)));
+    }
+
+    #[test]
+    fn errors_are_displayed() {
+        let messages = [
+            LayoutError::InvalidInputNode(3),
+            LayoutError::MissingNodeData(3),
+            LayoutError::DetachedNode(3),
+            LayoutError::DisabledAlgorithm(Display::None),
+            LayoutError::WriteFailed(io::ErrorKind::WriteZero),
+        ]
+        .map(|error| error.to_string());
+        assert_eq!(
+            messages,
+            [
+                "node 3 was not found in the composer",
+                "node 3 has no layout data",
+                "node 3 is detached from its parent",
+                "layout algorithm for None is disabled",
+                "failed to write layout tree: write zero",
+            ]
+        );
+    }
+
+    #[cfg(feature = "detailed_layout_info")]
+    #[test]
+    fn grid_tracks_are_forgotten_by_other_algorithms() {
+        fn app(s: Scope<Root, ()>) {
+            crate::widgets::grid(s, modifier().size(100.0, 100.0), |s| {
+                leaf(s, modifier().height(20.0));
+            });
+        }
+        let mut recomposer = Composer::compose(app, TaffyConfig::default());
+        let root = recomposer.root_node_key();
+        recomposer.compute_layout(SPACE).unwrap();
+        let info = recomposer.detailed_layout_info(root).unwrap();
+        assert!(matches!(info, DetailedLayoutInfo::Grid(_)));
+
+        recomposer.with_composer_mut(|composer| {
+            let data = composer.nodes[root].data.as_mut().unwrap();
+            data.set_style(modifier().size(100.0, 100.0).flex_column());
+        });
+        recomposer.compute_layout(SPACE).unwrap();
+        let info = recomposer.detailed_layout_info(root).unwrap();
+        assert_eq!(info, DetailedLayoutInfo::None);
+    }
+
+    /// The display modes disabled here are enabled in taffy by the dev-dependency.
+    #[cfg(not(all(feature = "block_layout", feature = "grid")))]
+    mod display_fallback {
+        use super::*;
+        use crate::geometry::AbsoluteLayout;
+        use crate::widgets::container;
+
+        /// Lays out the app and returns the absolute layouts of the nodes in pre-order.
+        fn layouts(
+            app: fn(Scope<Root, ()>),
+            display_fallback: DisplayFallback,
+        ) -> LayoutResult<Vec<AbsoluteLayout>> {
+            let config = TaffyConfig::default().with_display_fallback(display_fallback);
+            let mut recomposer = Composer::compose(app, config);
+            recomposer.compute_layout(SPACE)?;
+            let layouts = recomposer.absolute_layouts()?;
+            Ok(layouts.into_iter().map(|(_, layout)| layout).collect())
+        }
+
+        #[cfg(not(feature = "block_layout"))]
+        #[test]
+        fn error_fails_for_containers_and_measures_leaves() {
+            fn app(s: Scope<Root, ()>) {
+                column(s, modifier().size(100.0, 100.0), |s| {
+                    container(s, modifier().display(Display::Block), |s| {
+                        leaf(s, modifier().size(10.0, 10.0));
+                    });
+                });
+            }
+            fn leaf_app(s: Scope<Root, ()>) {
+                column(s, modifier().size(100.0, 100.0), |s| {
+                    leaf(s, modifier().display(Display::Block).size(10.0, 10.0));
+                });
+            }
+            assert_eq!(
+                layouts(app, DisplayFallback::Error),
+                Err(LayoutError::DisabledAlgorithm(Display::Block))
+            );
+            let layouts = layouts(leaf_app, DisplayFallback::Error).unwrap();
+            assert_eq!(
+                layouts[1].size(),
+                Size {
+                    width: 10.0,
+                    height: 10.0
+                }
+            );
+        }
+
+        #[cfg(all(feature = "block_layout", not(feature = "grid")))]
+        #[test]
+        fn block_stacks_the_children() {
+            fn app(s: Scope<Root, ()>) {
+                column(s, modifier().size(100.0, 100.0), |s| {
+                    container(s, modifier().display(Display::Grid), |s| {
+                        leaf(s, modifier().height(10.0));
+                        leaf(s, modifier().height(10.0));
+                    });
+                });
+            }
+            let layouts = layouts(app, DisplayFallback::Block).unwrap();
+            assert_eq!(layouts[2].location, point(0.0, 0.0));
+            assert_eq!(layouts[3].location, point(0.0, 10.0));
+            assert_eq!(layouts[3].size().width, 100.0);
+        }
+
+        #[cfg(not(feature = "block_layout"))]
+        #[test]
+        fn flex_lays_the_children_out_in_a_row() {
+            fn app(s: Scope<Root, ()>) {
+                column(s, modifier().size(100.0, 100.0), |s| {
+                    container(s, modifier().display(Display::Block), |s| {
+                        leaf(s, modifier().size(10.0, 10.0));
+                        leaf(s, modifier().size(10.0, 10.0));
+                    });
+                });
+            }
+            let layouts = layouts(app, DisplayFallback::Flex).unwrap();
+            assert_eq!(layouts[2].location, point(0.0, 0.0));
+            assert_eq!(layouts[3].location, point(10.0, 0.0));
+        }
+
+        #[cfg(not(feature = "block_layout"))]
+        #[test]
+        fn hidden_hides_the_container() {
+            fn app(s: Scope<Root, ()>) {
+                column(s, modifier().size(100.0, 100.0), |s| {
+                    container(
+                        s,
+                        modifier().display(Display::Block).size(50.0, 50.0),
+                        |s| {
+                            leaf(s, modifier().size(10.0, 10.0));
+                        },
+                    );
+                });
+            }
+            let layouts = layouts(app, DisplayFallback::Hidden).unwrap();
+            assert_eq!(layouts.len(), 2);
+            assert_eq!(layouts[1].size(), Size::ZERO);
+        }
+    }
 }
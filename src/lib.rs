@@ -2,11 +2,48 @@ pub mod traits;
 
 pub mod impls;
 
+pub mod modifier;
+
+pub mod text;
+
+pub mod widgets;
+
+mod macros;
+
 mod taffy_tree;
 pub use taffy_tree::{TaffyTree, TaffyTreeChildIter, TaffyTreeView};
 
+mod animation;
+pub use animation::{Easing, LayoutAnimation, LayoutTransition};
+
+mod changes;
+pub use changes::LayoutChange;
+
+mod geometry;
+pub use geometry::AbsoluteLayout;
+
+mod hit_test;
+
+#[cfg(feature = "rayon")]
+mod parallel;
+#[cfg(feature = "rayon")]
+pub use parallel::ParallelLayout;
+
+mod print;
+
+mod rounding;
+pub use rounding::PixelSnapping;
+
+mod scroll;
+
+#[cfg(all(test, feature = "flexbox"))]
+mod test_utils;
+
+mod snapshot;
+pub use snapshot::{assert_layout_snapshot, LayoutSnapshot, UPDATE_SNAPSHOTS_ENV};
+
 mod layout;
 pub use compose_rt;
-pub use layout::{LayoutError, LayoutResult, TaffyLayout};
+pub use layout::{DisplayFallback, LayoutError, LayoutResult, TaffyLayout};
 /// re-exports
 pub use taffy;
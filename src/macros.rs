@@ -0,0 +1,190 @@
+/// Composes a tree of widgets declaratively.
+///
+/// Every node is a call to a composable taking the scope as its first argument, the scope
+/// is passed implicitly. A node followed by a block is a container, its children are passed
+/// as the trailing `content` argument. Nodes are separated by commas.
+///
+/// ```
+/// use compose_rt::{Composer, Root};
+/// use compose_taffy::impls::{Scope, TaffyConfig};
+/// use compose_taffy::layout;
+/// use compose_taffy::modifier::{modifier, StyleModifier};
+/// use compose_taffy::widgets::{column, leaf, row, spacer};
+///
+/// fn app(s: Scope<Root, ()>) {
+///     layout!(s,
+///         column(modifier().size(100.0, 100.0)) {
+///             row(modifier().height(20.0)) {
+///                 leaf(modifier().width(20.0)),
+///                 spacer(),
+///                 leaf(modifier().width(20.0)),
+///             },
+///             #[key(7)] leaf(modifier().flex_grow(1.0)),
+///         }
+///     );
+/// }
+///
+/// let _recomposer = Composer::compose(app, TaffyConfig::default());
+/// ```
+///
+/// All nodes expanded from one invocation share its call site, so every node is given a
+/// `usize` key through `Scope::key` derived from its position and the key of its parent,
+/// which keeps the identities of the nodes distinct. Prefix a node with `#[key(..)]` to
+/// key it explicitly instead, e.g. when composing a list inside a loop. Explicit keys
+/// never collide with derived ones, the top bit of the key is reserved for that. The arguments of
+/// a node are evaluated inside the `'static` closure passed to `Scope::key`, so captured
+/// values which are not `Copy` have to be cloned.
+#[macro_export]
+macro_rules! layout {
+    ($s:expr, $($nodes:tt)*) => {{
+        let __scope = $s;
+        $crate::__layout_nodes!(__scope, 0usize, 0usize; $($nodes)*);
+    }};
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __layout_nodes {
+    ($s:ident, $parent:expr, $index:expr; $(,)?) => {};
+    (
+        $s:ident, $parent:expr, $index:expr;
+        #[key($key:expr)]
+        $($name:ident)::+ ($($args:expr),* $(,)?) { $($children:tt)* }
+        $(, $($rest:tt)*)?
+    ) => {
+        {
+            let __key = $crate::__layout_key!(explicit $key);
+            $crate::__layout_node!($s, __key; $($name)::+ ($($args),*) { $($children)* });
+        }
+        $crate::__layout_nodes!($s, $parent, $index + 1; $($($rest)*)?);
+    };
+    (
+        $s:ident, $parent:expr, $index:expr;
+        #[key($key:expr)]
+        $($name:ident)::+ ($($args:expr),* $(,)?)
+        $(, $($rest:tt)*)?
+    ) => {
+        {
+            let __key = $crate::__layout_key!(explicit $key);
+            $crate::__layout_node!($s, __key; $($name)::+ ($($args),*));
+        }
+        $crate::__layout_nodes!($s, $parent, $index + 1; $($($rest)*)?);
+    };
+    (
+        $s:ident, $parent:expr, $index:expr;
+        $($name:ident)::+ ($($args:expr),* $(,)?) { $($children:tt)* }
+        $(, $($rest:tt)*)?
+    ) => {
+        {
+            let __key = $crate::__layout_key!(derived usize::wrapping_add(usize::wrapping_mul($parent, 31), $index + 1));
+            $crate::__layout_node!($s, __key; $($name)::+ ($($args),*) { $($children)* });
+        }
+        $crate::__layout_nodes!($s, $parent, $index + 1; $($($rest)*)?);
+    };
+    (
+        $s:ident, $parent:expr, $index:expr;
+        $($name:ident)::+ ($($args:expr),* $(,)?)
+        $(, $($rest:tt)*)?
+    ) => {
+        {
+            let __key = $crate::__layout_key!(derived usize::wrapping_add(usize::wrapping_mul($parent, 31), $index + 1));
+            $crate::__layout_node!($s, __key; $($name)::+ ($($args),*));
+        }
+        $crate::__layout_nodes!($s, $parent, $index + 1; $($($rest)*)?);
+    };
+}
+
+/// Keeps explicit and derived keys apart: derived keys have the top bit set, explicit
+/// keys have it cleared.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __layout_key {
+    (explicit $key:expr) => {{
+        let __key: usize = $key;
+        __key & !(1usize << (usize::BITS - 1))
+    }};
+    (derived $key:expr) => {
+        $key | (1usize << (usize::BITS - 1))
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __layout_node {
+    ($s:ident, $key:ident; $($name:ident)::+ ($($args:expr),*) { $($children:tt)* }) => {
+        $s.key($key, move |$s| {
+            $($name)::+($s, $($args,)* move |$s| {
+                $crate::__layout_nodes!($s, $key, 0usize; $($children)*);
+            });
+        });
+    };
+    ($s:ident, $key:ident; $($name:ident)::+ ($($args:expr),*)) => {
+        $s.key($key, move |$s| {
+            $($name)::+($s, $($args),*);
+        });
+    };
+}
+
+#[cfg(all(test, feature = "flexbox"))]
+mod tests {
+    use std::collections::HashSet;
+
+    use compose_rt::{Composer, Root};
+
+    use crate::impls::{Scope, TaffyConfig};
+    use crate::modifier::{modifier, StyleModifier};
+    use crate::widgets::{column, leaf, row, spacer};
+
+    #[test]
+    fn expanded_nodes_have_distinct_scopes() {
+        fn app(s: Scope<Root, ()>) {
+            layout!(s,
+                column(modifier().size(100.0, 100.0)) {
+                    row(modifier().height(20.0)) {
+                        leaf(modifier().width(20.0)),
+                        spacer(),
+                        leaf(modifier().width(20.0)),
+                    },
+                    #[key(7)] leaf(modifier().flex_grow(1.0)),
+                    leaf(modifier().flex_grow(1.0)),
+                }
+            );
+        }
+        let recomposer = Composer::compose(app, TaffyConfig::default());
+        let scopes = recomposer.with_composer(|composer| {
+            composer
+                .nodes
+                .iter()
+                .map(|(_, node)| format!("{:?}", node.scope_id))
+                .collect::<Vec<_>>()
+        });
+        assert_eq!(scopes.iter().collect::<HashSet<_>>().len(), scopes.len());
+    }
+
+    #[test]
+    fn explicit_keys_do_not_collide_with_derived_keys() {
+        fn app(s: Scope<Root, ()>) {
+            column(s, modifier().size(100.0, 100.0), |s| {
+                // the position of the second leaf is the key of the first one
+                layout!(
+                    s,
+                    #[key(2)]
+                    leaf(modifier().height(10.0)),
+                    leaf(modifier().height(20.0)),
+                    #[key(usize::MAX)]
+                    leaf(modifier().height(30.0)),
+                );
+            });
+        }
+        let recomposer = Composer::compose(app, TaffyConfig::default());
+        let scopes = recomposer.with_composer(|composer| {
+            composer
+                .nodes
+                .iter()
+                .map(|(_, node)| format!("{:?}", node.scope_id))
+                .collect::<Vec<_>>()
+        });
+        assert_eq!(scopes.len(), 5);
+        assert_eq!(scopes.iter().collect::<HashSet<_>>().len(), scopes.len());
+    }
+}
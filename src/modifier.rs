@@ -0,0 +1,736 @@
+//! A fluent builder for [`Style`], in the spirit of Jetpack Compose modifiers.
+//!
+//! ```
+//! use compose_taffy::modifier::{modifier, px, StyleModifier};
+//!
+//! let style = modifier()
+//!     .width(px(100.0))
+//!     .padding(8.0)
+//!     .justify_center()
+//!     .flex_grow(1.0);
+//! ```
+use taffy::style_helpers::{FromLength, FromPercent, TaffyAuto};
+#[cfg(feature = "block_layout")]
+use taffy::TextAlign;
+#[cfg(any(feature = "flexbox", feature = "grid"))]
+use taffy::{AlignContent, AlignItems, AlignSelf, JustifyContent};
+use taffy::{
+    BoxSizing, Dimension, Display, LengthPercentage, LengthPercentageAuto, Overflow, Point,
+    Position, Rect, Size, Style,
+};
+#[cfg(feature = "flexbox")]
+use taffy::{FlexDirection, FlexWrap};
+#[cfg(feature = "grid")]
+use taffy::{
+    GridAutoFlow, GridPlacement, Line, NonRepeatedTrackSizingFunction, TrackSizingFunction,
+};
+
+/// Starts a modifier chain from the default style.
+#[inline(always)]
+pub fn modifier() -> Style {
+    Style::DEFAULT
+}
+
+/// A length in layout units.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Px(pub f32);
+
+/// A length in layout units.
+#[inline(always)]
+pub fn px(value: f32) -> Px {
+    Px(value)
+}
+
+/// A fraction of the parent's size, `1.0` being 100%.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Percent(pub f32);
+
+/// A fraction of the parent's size, `1.0` being 100%.
+#[inline(always)]
+pub fn percent(value: f32) -> Percent {
+    Percent(value)
+}
+
+/// An automatically resolved length.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Auto;
+
+/// An automatically resolved length.
+#[inline(always)]
+pub fn auto() -> Auto {
+    Auto
+}
+
+/// A value accepted by the length properties of [`StyleModifier`], plain `f32`s are lengths in
+/// layout units.
+pub trait IntoStyleValue<T> {
+    fn into_style_value(self) -> T;
+}
+
+impl<T> IntoStyleValue<T> for f32
+where
+    T: FromLength,
+{
+    #[inline(always)]
+    fn into_style_value(self) -> T {
+        T::from_length(self)
+    }
+}
+
+impl<T> IntoStyleValue<T> for Px
+where
+    T: FromLength,
+{
+    #[inline(always)]
+    fn into_style_value(self) -> T {
+        T::from_length(self.0)
+    }
+}
+
+impl<T> IntoStyleValue<T> for Percent
+where
+    T: FromPercent,
+{
+    #[inline(always)]
+    fn into_style_value(self) -> T {
+        T::from_percent(self.0)
+    }
+}
+
+impl<T> IntoStyleValue<T> for Auto
+where
+    T: TaffyAuto,
+{
+    #[inline(always)]
+    fn into_style_value(self) -> T {
+        T::AUTO
+    }
+}
+
+impl IntoStyleValue<Dimension> for Dimension {
+    #[inline(always)]
+    fn into_style_value(self) -> Dimension {
+        self
+    }
+}
+
+impl IntoStyleValue<LengthPercentage> for LengthPercentage {
+    #[inline(always)]
+    fn into_style_value(self) -> LengthPercentage {
+        self
+    }
+}
+
+impl IntoStyleValue<LengthPercentageAuto> for LengthPercentageAuto {
+    #[inline(always)]
+    fn into_style_value(self) -> LengthPercentageAuto {
+        self
+    }
+}
+
+/// Chainable setters for every [`Style`] property, the flexbox, grid and block properties
+/// are gated by the same cargo features as `traits::TaffyNode`.
+pub trait StyleModifier: Sized {
+    fn display(self, display: Display) -> Self;
+    /// Shorthand for `Display::None`
+    fn hidden(self) -> Self;
+    fn box_sizing(self, box_sizing: BoxSizing) -> Self;
+    fn overflow(self, overflow: Overflow) -> Self;
+    fn overflow_x(self, overflow: Overflow) -> Self;
+    fn overflow_y(self, overflow: Overflow) -> Self;
+    fn scrollbar_width(self, width: f32) -> Self;
+
+    fn position(self, position: Position) -> Self;
+    /// Shorthand for `Position::Absolute`
+    fn absolute(self) -> Self;
+    fn inset(self, inset: impl IntoStyleValue<LengthPercentageAuto>) -> Self;
+    fn left(self, left: impl IntoStyleValue<LengthPercentageAuto>) -> Self;
+    fn right(self, right: impl IntoStyleValue<LengthPercentageAuto>) -> Self;
+    fn top(self, top: impl IntoStyleValue<LengthPercentageAuto>) -> Self;
+    fn bottom(self, bottom: impl IntoStyleValue<LengthPercentageAuto>) -> Self;
+
+    fn width(self, width: impl IntoStyleValue<Dimension>) -> Self;
+    fn height(self, height: impl IntoStyleValue<Dimension>) -> Self;
+    fn size(
+        self,
+        width: impl IntoStyleValue<Dimension>,
+        height: impl IntoStyleValue<Dimension>,
+    ) -> Self;
+    /// Shorthand for a width and height of 100%
+    fn fill_max_size(self) -> Self;
+    fn min_width(self, width: impl IntoStyleValue<Dimension>) -> Self;
+    fn min_height(self, height: impl IntoStyleValue<Dimension>) -> Self;
+    fn max_width(self, width: impl IntoStyleValue<Dimension>) -> Self;
+    fn max_height(self, height: impl IntoStyleValue<Dimension>) -> Self;
+    fn aspect_ratio(self, aspect_ratio: f32) -> Self;
+
+    fn margin(self, margin: impl IntoStyleValue<LengthPercentageAuto>) -> Self;
+    fn margin_x(self, margin: impl IntoStyleValue<LengthPercentageAuto>) -> Self;
+    fn margin_y(self, margin: impl IntoStyleValue<LengthPercentageAuto>) -> Self;
+    fn margin_left(self, margin: impl IntoStyleValue<LengthPercentageAuto>) -> Self;
+    fn margin_right(self, margin: impl IntoStyleValue<LengthPercentageAuto>) -> Self;
+    fn margin_top(self, margin: impl IntoStyleValue<LengthPercentageAuto>) -> Self;
+    fn margin_bottom(self, margin: impl IntoStyleValue<LengthPercentageAuto>) -> Self;
+
+    fn padding(self, padding: impl IntoStyleValue<LengthPercentage>) -> Self;
+    fn padding_x(self, padding: impl IntoStyleValue<LengthPercentage>) -> Self;
+    fn padding_y(self, padding: impl IntoStyleValue<LengthPercentage>) -> Self;
+    fn padding_left(self, padding: impl IntoStyleValue<LengthPercentage>) -> Self;
+    fn padding_right(self, padding: impl IntoStyleValue<LengthPercentage>) -> Self;
+    fn padding_top(self, padding: impl IntoStyleValue<LengthPercentage>) -> Self;
+    fn padding_bottom(self, padding: impl IntoStyleValue<LengthPercentage>) -> Self;
+
+    fn border(self, border: impl IntoStyleValue<LengthPercentage>) -> Self;
+
+    #[cfg(any(feature = "flexbox", feature = "grid"))]
+    fn gap(self, gap: impl IntoStyleValue<LengthPercentage>) -> Self;
+    #[cfg(any(feature = "flexbox", feature = "grid"))]
+    fn row_gap(self, gap: impl IntoStyleValue<LengthPercentage>) -> Self;
+    #[cfg(any(feature = "flexbox", feature = "grid"))]
+    fn column_gap(self, gap: impl IntoStyleValue<LengthPercentage>) -> Self;
+    #[cfg(any(feature = "flexbox", feature = "grid"))]
+    fn align_items(self, align_items: AlignItems) -> Self;
+    #[cfg(any(feature = "flexbox", feature = "grid"))]
+    fn align_self(self, align_self: AlignSelf) -> Self;
+    #[cfg(any(feature = "flexbox", feature = "grid"))]
+    fn align_content(self, align_content: AlignContent) -> Self;
+    #[cfg(any(feature = "flexbox", feature = "grid"))]
+    fn justify_content(self, justify_content: JustifyContent) -> Self;
+    /// Shorthand for `AlignItems::Center`
+    #[cfg(any(feature = "flexbox", feature = "grid"))]
+    fn align_center(self) -> Self;
+    /// Shorthand for `JustifyContent::Center`
+    #[cfg(any(feature = "flexbox", feature = "grid"))]
+    fn justify_center(self) -> Self;
+    /// Centers the children along both axes
+    #[cfg(any(feature = "flexbox", feature = "grid"))]
+    fn center(self) -> Self;
+
+    #[cfg(feature = "block_layout")]
+    fn text_align(self, text_align: TextAlign) -> Self;
+
+    #[cfg(feature = "flexbox")]
+    fn flex_direction(self, flex_direction: FlexDirection) -> Self;
+    /// Shorthand for a flexbox container with `FlexDirection::Row`
+    #[cfg(feature = "flexbox")]
+    fn flex_row(self) -> Self;
+    /// Shorthand for a flexbox container with `FlexDirection::Column`
+    #[cfg(feature = "flexbox")]
+    fn flex_column(self) -> Self;
+    #[cfg(feature = "flexbox")]
+    fn flex_wrap(self, flex_wrap: FlexWrap) -> Self;
+    #[cfg(feature = "flexbox")]
+    fn flex_basis(self, flex_basis: impl IntoStyleValue<Dimension>) -> Self;
+    #[cfg(feature = "flexbox")]
+    fn flex_grow(self, flex_grow: f32) -> Self;
+    #[cfg(feature = "flexbox")]
+    fn flex_shrink(self, flex_shrink: f32) -> Self;
+
+    #[cfg(feature = "grid")]
+    fn justify_items(self, justify_items: AlignItems) -> Self;
+    #[cfg(feature = "grid")]
+    fn justify_self(self, justify_self: AlignSelf) -> Self;
+    #[cfg(feature = "grid")]
+    fn grid_template_rows(self, rows: Vec<TrackSizingFunction>) -> Self;
+    #[cfg(feature = "grid")]
+    fn grid_template_columns(self, columns: Vec<TrackSizingFunction>) -> Self;
+    #[cfg(feature = "grid")]
+    fn grid_auto_rows(self, rows: Vec<NonRepeatedTrackSizingFunction>) -> Self;
+    #[cfg(feature = "grid")]
+    fn grid_auto_columns(self, columns: Vec<NonRepeatedTrackSizingFunction>) -> Self;
+    #[cfg(feature = "grid")]
+    fn grid_auto_flow(self, grid_auto_flow: GridAutoFlow) -> Self;
+    #[cfg(feature = "grid")]
+    fn grid_row(self, grid_row: Line<GridPlacement>) -> Self;
+    #[cfg(feature = "grid")]
+    fn grid_column(self, grid_column: Line<GridPlacement>) -> Self;
+}
+
+impl StyleModifier for Style {
+    #[inline(always)]
+    fn display(self, display: Display) -> Self {
+        Self { display, ..self }
+    }
+
+    #[inline(always)]
+    fn hidden(self) -> Self {
+        self.display(Display::None)
+    }
+
+    #[inline(always)]
+    fn box_sizing(self, box_sizing: BoxSizing) -> Self {
+        Self { box_sizing, ..self }
+    }
+
+    #[inline(always)]
+    fn overflow(self, overflow: Overflow) -> Self {
+        Self {
+            overflow: Point {
+                x: overflow,
+                y: overflow,
+            },
+            ..self
+        }
+    }
+
+    #[inline(always)]
+    fn overflow_x(mut self, overflow: Overflow) -> Self {
+        self.overflow.x = overflow;
+        self
+    }
+
+    #[inline(always)]
+    fn overflow_y(mut self, overflow: Overflow) -> Self {
+        self.overflow.y = overflow;
+        self
+    }
+
+    #[inline(always)]
+    fn scrollbar_width(self, scrollbar_width: f32) -> Self {
+        Self {
+            scrollbar_width,
+            ..self
+        }
+    }
+
+    #[inline(always)]
+    fn position(self, position: Position) -> Self {
+        Self { position, ..self }
+    }
+
+    #[inline(always)]
+    fn absolute(self) -> Self {
+        self.position(Position::Absolute)
+    }
+
+    #[inline(always)]
+    fn inset(self, inset: impl IntoStyleValue<LengthPercentageAuto>) -> Self {
+        Self {
+            inset: uniform(inset.into_style_value()),
+            ..self
+        }
+    }
+
+    #[inline(always)]
+    fn left(mut self, left: impl IntoStyleValue<LengthPercentageAuto>) -> Self {
+        self.inset.left = left.into_style_value();
+        self
+    }
+
+    #[inline(always)]
+    fn right(mut self, right: impl IntoStyleValue<LengthPercentageAuto>) -> Self {
+        self.inset.right = right.into_style_value();
+        self
+    }
+
+    #[inline(always)]
+    fn top(mut self, top: impl IntoStyleValue<LengthPercentageAuto>) -> Self {
+        self.inset.top = top.into_style_value();
+        self
+    }
+
+    #[inline(always)]
+    fn bottom(mut self, bottom: impl IntoStyleValue<LengthPercentageAuto>) -> Self {
+        self.inset.bottom = bottom.into_style_value();
+        self
+    }
+
+    #[inline(always)]
+    fn width(mut self, width: impl IntoStyleValue<Dimension>) -> Self {
+        self.size.width = width.into_style_value();
+        self
+    }
+
+    #[inline(always)]
+    fn height(mut self, height: impl IntoStyleValue<Dimension>) -> Self {
+        self.size.height = height.into_style_value();
+        self
+    }
+
+    #[inline(always)]
+    fn size(
+        self,
+        width: impl IntoStyleValue<Dimension>,
+        height: impl IntoStyleValue<Dimension>,
+    ) -> Self {
+        Self {
+            size: Size {
+                width: width.into_style_value(),
+                height: height.into_style_value(),
+            },
+            ..self
+        }
+    }
+
+    #[inline(always)]
+    fn fill_max_size(self) -> Self {
+        self.size(Dimension::Percent(1.0), Dimension::Percent(1.0))
+    }
+
+    #[inline(always)]
+    fn min_width(mut self, width: impl IntoStyleValue<Dimension>) -> Self {
+        self.min_size.width = width.into_style_value();
+        self
+    }
+
+    #[inline(always)]
+    fn min_height(mut self, height: impl IntoStyleValue<Dimension>) -> Self {
+        self.min_size.height = height.into_style_value();
+        self
+    }
+
+    #[inline(always)]
+    fn max_width(mut self, width: impl IntoStyleValue<Dimension>) -> Self {
+        self.max_size.width = width.into_style_value();
+        self
+    }
+
+    #[inline(always)]
+    fn max_height(mut self, height: impl IntoStyleValue<Dimension>) -> Self {
+        self.max_size.height = height.into_style_value();
+        self
+    }
+
+    #[inline(always)]
+    fn aspect_ratio(self, aspect_ratio: f32) -> Self {
+        Self {
+            aspect_ratio: Some(aspect_ratio),
+            ..self
+        }
+    }
+
+    #[inline(always)]
+    fn margin(self, margin: impl IntoStyleValue<LengthPercentageAuto>) -> Self {
+        Self {
+            margin: uniform(margin.into_style_value()),
+            ..self
+        }
+    }
+
+    #[inline(always)]
+    fn margin_x(mut self, margin: impl IntoStyleValue<LengthPercentageAuto>) -> Self {
+        let margin = margin.into_style_value();
+        self.margin.left = margin;
+        self.margin.right = margin;
+        self
+    }
+
+    #[inline(always)]
+    fn margin_y(mut self, margin: impl IntoStyleValue<LengthPercentageAuto>) -> Self {
+        let margin = margin.into_style_value();
+        self.margin.top = margin;
+        self.margin.bottom = margin;
+        self
+    }
+
+    #[inline(always)]
+    fn margin_left(mut self, margin: impl IntoStyleValue<LengthPercentageAuto>) -> Self {
+        self.margin.left = margin.into_style_value();
+        self
+    }
+
+    #[inline(always)]
+    fn margin_right(mut self, margin: impl IntoStyleValue<LengthPercentageAuto>) -> Self {
+        self.margin.right = margin.into_style_value();
+        self
+    }
+
+    #[inline(always)]
+    fn margin_top(mut self, margin: impl IntoStyleValue<LengthPercentageAuto>) -> Self {
+        self.margin.top = margin.into_style_value();
+        self
+    }
+
+    #[inline(always)]
+    fn margin_bottom(mut self, margin: impl IntoStyleValue<LengthPercentageAuto>) -> Self {
+        self.margin.bottom = margin.into_style_value();
+        self
+    }
+
+    #[inline(always)]
+    fn padding(self, padding: impl IntoStyleValue<LengthPercentage>) -> Self {
+        Self {
+            padding: uniform(padding.into_style_value()),
+            ..self
+        }
+    }
+
+    #[inline(always)]
+    fn padding_x(mut self, padding: impl IntoStyleValue<LengthPercentage>) -> Self {
+        let padding = padding.into_style_value();
+        self.padding.left = padding;
+        self.padding.right = padding;
+        self
+    }
+
+    #[inline(always)]
+    fn padding_y(mut self, padding: impl IntoStyleValue<LengthPercentage>) -> Self {
+        let padding = padding.into_style_value();
+        self.padding.top = padding;
+        self.padding.bottom = padding;
+        self
+    }
+
+    #[inline(always)]
+    fn padding_left(mut self, padding: impl IntoStyleValue<LengthPercentage>) -> Self {
+        self.padding.left = padding.into_style_value();
+        self
+    }
+
+    #[inline(always)]
+    fn padding_right(mut self, padding: impl IntoStyleValue<LengthPercentage>) -> Self {
+        self.padding.right = padding.into_style_value();
+        self
+    }
+
+    #[inline(always)]
+    fn padding_top(mut self, padding: impl IntoStyleValue<LengthPercentage>) -> Self {
+        self.padding.top = padding.into_style_value();
+        self
+    }
+
+    #[inline(always)]
+    fn padding_bottom(mut self, padding: impl IntoStyleValue<LengthPercentage>) -> Self {
+        self.padding.bottom = padding.into_style_value();
+        self
+    }
+
+    #[inline(always)]
+    fn border(self, border: impl IntoStyleValue<LengthPercentage>) -> Self {
+        Self {
+            border: uniform(border.into_style_value()),
+            ..self
+        }
+    }
+
+    #[cfg(any(feature = "flexbox", feature = "grid"))]
+    #[inline(always)]
+    fn gap(self, gap: impl IntoStyleValue<LengthPercentage>) -> Self {
+        let gap = gap.into_style_value();
+        Self {
+            gap: Size {
+                width: gap,
+                height: gap,
+            },
+            ..self
+        }
+    }
+
+    #[cfg(any(feature = "flexbox", feature = "grid"))]
+    #[inline(always)]
+    fn row_gap(mut self, gap: impl IntoStyleValue<LengthPercentage>) -> Self {
+        self.gap.height = gap.into_style_value();
+        self
+    }
+
+    #[cfg(any(feature = "flexbox", feature = "grid"))]
+    #[inline(always)]
+    fn column_gap(mut self, gap: impl IntoStyleValue<LengthPercentage>) -> Self {
+        self.gap.width = gap.into_style_value();
+        self
+    }
+
+    #[cfg(any(feature = "flexbox", feature = "grid"))]
+    #[inline(always)]
+    fn align_items(self, align_items: AlignItems) -> Self {
+        Self {
+            align_items: Some(align_items),
+            ..self
+        }
+    }
+
+    #[cfg(any(feature = "flexbox", feature = "grid"))]
+    #[inline(always)]
+    fn align_self(self, align_self: AlignSelf) -> Self {
+        Self {
+            align_self: Some(align_self),
+            ..self
+        }
+    }
+
+    #[cfg(any(feature = "flexbox", feature = "grid"))]
+    #[inline(always)]
+    fn align_content(self, align_content: AlignContent) -> Self {
+        Self {
+            align_content: Some(align_content),
+            ..self
+        }
+    }
+
+    #[cfg(any(feature = "flexbox", feature = "grid"))]
+    #[inline(always)]
+    fn justify_content(self, justify_content: JustifyContent) -> Self {
+        Self {
+            justify_content: Some(justify_content),
+            ..self
+        }
+    }
+
+    #[cfg(any(feature = "flexbox", feature = "grid"))]
+    #[inline(always)]
+    fn align_center(self) -> Self {
+        self.align_items(AlignItems::Center)
+    }
+
+    #[cfg(any(feature = "flexbox", feature = "grid"))]
+    #[inline(always)]
+    fn justify_center(self) -> Self {
+        self.justify_content(JustifyContent::Center)
+    }
+
+    #[cfg(any(feature = "flexbox", feature = "grid"))]
+    #[inline(always)]
+    fn center(self) -> Self {
+        self.align_center().justify_center()
+    }
+
+    #[cfg(feature = "block_layout")]
+    #[inline(always)]
+    fn text_align(self, text_align: TextAlign) -> Self {
+        Self { text_align, ..self }
+    }
+
+    #[cfg(feature = "flexbox")]
+    #[inline(always)]
+    fn flex_direction(self, flex_direction: FlexDirection) -> Self {
+        Self {
+            flex_direction,
+            ..self
+        }
+    }
+
+    #[cfg(feature = "flexbox")]
+    #[inline(always)]
+    fn flex_row(self) -> Self {
+        self.display(Display::Flex)
+            .flex_direction(FlexDirection::Row)
+    }
+
+    #[cfg(feature = "flexbox")]
+    #[inline(always)]
+    fn flex_column(self) -> Self {
+        self.display(Display::Flex)
+            .flex_direction(FlexDirection::Column)
+    }
+
+    #[cfg(feature = "flexbox")]
+    #[inline(always)]
+    fn flex_wrap(self, flex_wrap: FlexWrap) -> Self {
+        Self { flex_wrap, ..self }
+    }
+
+    #[cfg(feature = "flexbox")]
+    #[inline(always)]
+    fn flex_basis(self, flex_basis: impl IntoStyleValue<Dimension>) -> Self {
+        Self {
+            flex_basis: flex_basis.into_style_value(),
+            ..self
+        }
+    }
+
+    #[cfg(feature = "flexbox")]
+    #[inline(always)]
+    fn flex_grow(self, flex_grow: f32) -> Self {
+        Self { flex_grow, ..self }
+    }
+
+    #[cfg(feature = "flexbox")]
+    #[inline(always)]
+    fn flex_shrink(self, flex_shrink: f32) -> Self {
+        Self {
+            flex_shrink,
+            ..self
+        }
+    }
+
+    #[cfg(feature = "grid")]
+    #[inline(always)]
+    fn justify_items(self, justify_items: AlignItems) -> Self {
+        Self {
+            justify_items: Some(justify_items),
+            ..self
+        }
+    }
+
+    #[cfg(feature = "grid")]
+    #[inline(always)]
+    fn justify_self(self, justify_self: AlignSelf) -> Self {
+        Self {
+            justify_self: Some(justify_self),
+            ..self
+        }
+    }
+
+    #[cfg(feature = "grid")]
+    #[inline(always)]
+    fn grid_template_rows(self, grid_template_rows: Vec<TrackSizingFunction>) -> Self {
+        Self {
+            grid_template_rows,
+            ..self
+        }
+    }
+
+    #[cfg(feature = "grid")]
+    #[inline(always)]
+    fn grid_template_columns(self, grid_template_columns: Vec<TrackSizingFunction>) -> Self {
+        Self {
+            grid_template_columns,
+            ..self
+        }
+    }
+
+    #[cfg(feature = "grid")]
+    #[inline(always)]
+    fn grid_auto_rows(self, grid_auto_rows: Vec<NonRepeatedTrackSizingFunction>) -> Self {
+        Self {
+            grid_auto_rows,
+            ..self
+        }
+    }
+
+    #[cfg(feature = "grid")]
+    #[inline(always)]
+    fn grid_auto_columns(self, grid_auto_columns: Vec<NonRepeatedTrackSizingFunction>) -> Self {
+        Self {
+            grid_auto_columns,
+            ..self
+        }
+    }
+
+    #[cfg(feature = "grid")]
+    #[inline(always)]
+    fn grid_auto_flow(self, grid_auto_flow: GridAutoFlow) -> Self {
+        Self {
+            grid_auto_flow,
+            ..self
+        }
+    }
+
+    #[cfg(feature = "grid")]
+    #[inline(always)]
+    fn grid_row(self, grid_row: Line<GridPlacement>) -> Self {
+        Self { grid_row, ..self }
+    }
+
+    #[cfg(feature = "grid")]
+    #[inline(always)]
+    fn grid_column(self, grid_column: Line<GridPlacement>) -> Self {
+        Self {
+            grid_column,
+            ..self
+        }
+    }
+}
+
+#[inline(always)]
+fn uniform<T>(value: T) -> Rect<T>
+where
+    T: Copy,
+{
+    Rect {
+        left: value,
+        right: value,
+        top: value,
+        bottom: value,
+    }
+}
@@ -0,0 +1,294 @@
+use compose_rt::{Composer, NodeKey, Recomposer};
+use rayon::prelude::*;
+use taffy::style_helpers::TaffyMaxContent;
+#[cfg(feature = "detailed_layout_info")]
+use taffy::DetailedLayoutInfo;
+use taffy::{
+    AvailableSpace, Dimension, Layout, LayoutInput, LayoutOutput, LayoutPartialTree,
+    LengthPercentage, NodeId, Size, SizingMode, Style,
+};
+
+use crate::geometry::{is_hidden, layout_children};
+use crate::impls::LayoutNode;
+use crate::layout::{perform_layouts, prepare_layout, resolve_display, LayoutPass};
+use crate::traits::{measure_node_context, IntoNodeId, Measure, TaffyConfig, TaffyNode};
+use crate::{LayoutChange, LayoutResult, TaffyTree};
+
+pub trait ParallelLayout {
+    /// Computes the layout of the root node like `TaffyLayout::compute_layout_measured`,
+    /// laying out the subtrees whose outer size is fixed by their style on the rayon
+    /// thread pool.
+    ///
+    /// A subtree qualifies when its root has children and lengths for its size, with
+    /// no percentages in its min and max sizes, padding and border, and no node in it
+    /// publishes its constraints. The subtrees are copied out of the composer together with
+    /// clones of their node contexts, and merged back when the serial pass reaches them.
+    fn compute_layout_parallel(
+        &mut self,
+        available_space: Size<AvailableSpace>,
+    ) -> LayoutResult<Vec<LayoutChange>>;
+}
+
+impl<S, C> ParallelLayout for Recomposer<S, LayoutNode<C>>
+where
+    S: 'static,
+    C: Measure + Clone + Send + 'static,
+{
+    fn compute_layout_parallel(
+        &mut self,
+        available_space: Size<AvailableSpace>,
+    ) -> LayoutResult<Vec<LayoutChange>> {
+        let node_key = self.root_node_key();
+        self.with_composer_mut(|composer| {
+            prepare_layout(composer, node_key)?;
+            let subtrees = fixed_size_subtrees(composer, node_key)
+                .into_iter()
+                .filter_map(|subtree_key| Subtree::copy(composer, subtree_key))
+                .collect::<Vec<_>>();
+            let mut tree = TaffyTree::new(composer, measure_node_context);
+            tree.subtrees = subtrees
+                .into_par_iter()
+                .map(|subtree| (subtree.keys[0], subtree.compute()))
+                .collect();
+            Ok(perform_layouts(&mut tree, node_key, available_space, None))
+        })
+        .map(LayoutPass::publish)
+    }
+}
+
+/// The layout of a subtree computed on a worker thread.
+pub(crate) struct SubtreeLayout {
+    size: Size<f32>,
+    #[cfg_attr(not(feature = "content_size"), allow(dead_code))]
+    content_size: Size<f32>,
+    /// The unrounded layouts of the descendants
+    layouts: Vec<(NodeKey, Layout)>,
+    #[cfg(feature = "detailed_layout_info")]
+    detailed_layout_infos: Vec<(NodeKey, DetailedLayoutInfo)>,
+}
+
+impl SubtreeLayout {
+    /// Returns whether the layout was computed for the size the parent lays the root out
+    /// with. Any other call, e.g. measuring the min-content size of a flex item or laying it
+    /// out shrunk or stretched, is computed in the serial pass.
+    #[inline(always)]
+    pub(crate) fn fits(&self, inputs: &LayoutInput) -> bool {
+        inputs.sizing_mode == SizingMode::InherentSize
+            && inputs.known_dimensions == self.size.map(Some)
+    }
+
+    /// The output of the root of the subtree.
+    #[inline(always)]
+    pub(crate) fn output(&self) -> LayoutOutput {
+        #[cfg(feature = "content_size")]
+        return LayoutOutput::from_sizes(self.size, self.content_size);
+        #[cfg(not(feature = "content_size"))]
+        LayoutOutput::from_outer_size(self.size)
+    }
+
+    /// Writes the layouts of the descendants to the tree.
+    pub(crate) fn merge<T, M>(&self, tree: &mut TaffyTree<'_, T, M>)
+    where
+        T: TaffyNode,
+        T::Context: TaffyConfig,
+        M: FnMut(
+            Size<Option<f32>>,
+            Size<AvailableSpace>,
+            NodeId,
+            Option<&mut T::NodeContext>,
+            &T::CoreContainerStyle,
+        ) -> Size<f32>,
+    {
+        for (node_key, layout) in &self.layouts {
+            tree.set_unrounded_layout(node_key.into_node_id(), layout);
+        }
+        #[cfg(feature = "detailed_layout_info")]
+        for (node_key, info) in &self.detailed_layout_infos {
+            if let Some(data) = tree.composer.nodes[*node_key].data.as_mut() {
+                data.set_detailed_layout_info(info.clone());
+            }
+        }
+    }
+}
+
+/// A copy of a subtree which can be laid out on another thread.
+struct Subtree<C> {
+    taffy: taffy::TaffyTree<C>,
+    /// The node keys by node id of the copy, the root first
+    keys: Vec<NodeKey>,
+    ids: Vec<NodeId>,
+}
+
+impl<C> Subtree<C>
+where
+    C: Measure + Clone + 'static,
+{
+    /// Copies the subtree, `None` when a node publishes its constraints.
+    fn copy(composer: &Composer<LayoutNode<C>>, node_key: NodeKey) -> Option<Self> {
+        let mut subtree = Self {
+            taffy: taffy::TaffyTree::new(),
+            keys: Vec::new(),
+            ids: Vec::new(),
+        };
+        subtree.taffy.disable_rounding();
+        let root = subtree.copy_node(composer, node_key)?;
+        debug_assert_eq!(subtree.ids[0], root);
+        Some(subtree)
+    }
+
+    fn copy_node(
+        &mut self,
+        composer: &Composer<LayoutNode<C>>,
+        node_key: NodeKey,
+    ) -> Option<NodeId> {
+        let data = composer.nodes[node_key].data.as_ref()?;
+        if data.get_constraints_state().is_some() {
+            return None;
+        }
+        let fallback = composer.context.display_fallback();
+        // only leaves can have an unresolved display mode, which taffy measures whatever it is
+        let style = Style {
+            display: resolve_display(data.style.display, fallback).unwrap_or(data.style.display),
+            ..data.style.clone()
+        };
+        let index = self.keys.len();
+        self.keys.push(node_key);
+        self.ids.push(NodeId::from(0u64));
+        let children = layout_children(composer, node_key).collect::<Vec<_>>();
+        let node_id = if children.is_empty() {
+            match &data.context {
+                Some(context) => self.taffy.new_leaf_with_context(style, context.clone()),
+                None => self.taffy.new_leaf(style),
+            }
+            .ok()?
+        } else {
+            let children = children
+                .into_iter()
+                .map(|child_key| self.copy_node(composer, child_key))
+                .collect::<Option<Vec<_>>>()?;
+            self.taffy.new_with_children(style, &children).ok()?
+        };
+        self.ids[index] = node_id;
+        Some(node_id)
+    }
+
+    #[inline(always)]
+    fn layout(&self, node_id: NodeId) -> &Layout {
+        self.taffy.layout(node_id).expect("the node is in the copy")
+    }
+
+    fn compute(mut self) -> SubtreeLayout {
+        let root = self.ids[0];
+        self.taffy
+            .compute_layout_with_measure(root, Size::MAX_CONTENT, measure_node_context)
+            .expect("the copy of the subtree is a valid tree");
+        let root_layout = self.layout(root);
+        SubtreeLayout {
+            size: root_layout.size,
+            #[cfg(feature = "content_size")]
+            content_size: root_layout.content_size,
+            #[cfg(not(feature = "content_size"))]
+            content_size: Size::ZERO,
+            layouts: self.keys[1..]
+                .iter()
+                .zip(&self.ids[1..])
+                .map(|(&node_key, &node_id)| (node_key, *self.layout(node_id)))
+                .collect(),
+            #[cfg(feature = "detailed_layout_info")]
+            detailed_layout_infos: self
+                .keys
+                .iter()
+                .zip(&self.ids)
+                .map(|(&node_key, &node_id)| {
+                    (node_key, self.taffy.detailed_layout_info(node_id).clone())
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Returns the outermost subtrees below the root which need layout and whose outer size
+/// does not depend on their parent.
+fn fixed_size_subtrees<T>(composer: &Composer<LayoutNode<T>>, node_key: NodeKey) -> Vec<NodeKey>
+where
+    T: 'static,
+{
+    let mut subtrees = Vec::new();
+    if composer.nodes[node_key].data.is_none() {
+        return subtrees;
+    }
+    let mut stack = layout_children(composer, node_key).collect::<Vec<_>>();
+    while let Some(node_key) = stack.pop() {
+        let Some(data) = composer.nodes[node_key].data.as_ref() else {
+            continue;
+        };
+        let is_leaf = layout_children(composer, node_key).next().is_none();
+        if is_leaf || is_hidden(composer, data) {
+            continue;
+        }
+        // the cache of a node is cleared when it or one of its descendants changed
+        if !data.cache.is_empty() {
+            continue;
+        }
+        if is_fixed_size(&data.style) {
+            subtrees.push(node_key);
+        } else {
+            stack.extend(layout_children(composer, node_key));
+        }
+    }
+    subtrees
+}
+
+/// Returns whether the outer size of a node with this style is independent of its parent.
+fn is_fixed_size(style: &Style) -> bool {
+    let is_length = |value: Dimension| matches!(value, Dimension::Length(_));
+    let is_length_or_auto =
+        |value: Dimension| matches!(value, Dimension::Length(_) | Dimension::Auto);
+    let is_fixed = |value: LengthPercentage| matches!(value, LengthPercentage::Length(_));
+    is_length(style.size.width)
+        && is_length(style.size.height)
+        && is_length_or_auto(style.min_size.width)
+        && is_length_or_auto(style.min_size.height)
+        && is_length_or_auto(style.max_size.width)
+        && is_length_or_auto(style.max_size.height)
+        && [style.padding, style.border].iter().all(|rect| {
+            [rect.left, rect.right, rect.top, rect.bottom]
+                .into_iter()
+                .all(is_fixed)
+        })
+}
+
+#[cfg(all(test, feature = "flexbox"))]
+mod tests {
+    use compose_rt::{Composer, Root};
+
+    use super::*;
+    use crate::impls::{Scope, TaffyConfig};
+    use crate::modifier::{modifier, StyleModifier};
+    use crate::test_utils::SPACE;
+    use crate::widgets::{column, leaf, row};
+    use crate::TaffyLayout;
+
+    #[test]
+    fn shrunk_subtrees_match_the_serial_layout() {
+        // the panels do not fit the row and shrink to 50 wide each
+        fn app(s: Scope<Root, ()>) {
+            row(s, modifier().size(100.0, 100.0), |s| {
+                for index in 0..2 {
+                    s.key(index, |s| {
+                        column(s, modifier().size(80.0, 50.0), |s| {
+                            leaf(s, modifier().height(10.0));
+                        });
+                    });
+                }
+            });
+        }
+        let mut serial = Composer::compose(app, TaffyConfig::default());
+        serial.compute_layout_measured(SPACE).unwrap();
+        let mut parallel = Composer::compose(app, TaffyConfig::default());
+        parallel.compute_layout_parallel(SPACE).unwrap();
+        let snapshot = serial.layout_snapshot().unwrap();
+        assert_eq!(snapshot.children[1].size.width, 50.0);
+        assert_eq!(parallel.layout_snapshot().unwrap(), snapshot);
+    }
+}
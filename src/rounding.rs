@@ -0,0 +1,220 @@
+use taffy::{NodeId, RoundTree};
+
+/// The distance in device pixels below which a value counts as on the pixel grid.
+const SNAP_EPSILON: f32 = 1e-3;
+
+/// How layout values are snapped to the device pixel grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum PixelSnapping {
+    /// Round to the nearest device pixel.
+    #[default]
+    Round,
+    /// Round down to the device pixel before.
+    Floor,
+    /// Round up to the device pixel after.
+    Ceil,
+}
+
+impl PixelSnapping {
+    /// Snaps a value in logical units to the grid of `1.0 / scale_factor` units. Values
+    /// within rounding error of a device pixel snap to it regardless of the mode.
+    /// A scale factor which is not a positive finite number is treated as `1.0`.
+    #[inline(always)]
+    pub fn snap(self, value: f32, scale_factor: f32) -> f32 {
+        let scale_factor = valid_scale_factor(scale_factor);
+        let value = value * scale_factor;
+        let nearest = value.round();
+        if (value - nearest).abs() < SNAP_EPSILON {
+            return nearest / scale_factor;
+        }
+        let snapped = match self {
+            PixelSnapping::Round => value.round(),
+            PixelSnapping::Floor => value.floor(),
+            PixelSnapping::Ceil => value.ceil(),
+        };
+        snapped / scale_factor
+    }
+}
+
+/// Returns the scale factor, or `1.0` when it is not a positive finite number.
+#[inline(always)]
+pub(crate) fn valid_scale_factor(scale_factor: f32) -> f32 {
+    if scale_factor.is_finite() && scale_factor > 0.0 {
+        scale_factor
+    } else {
+        1.0
+    }
+}
+
+/// Snaps the unrounded layouts of the subtree to device pixels and sets them as the final
+/// layouts, like `taffy::round_layout` at a scale factor of one with `PixelSnapping::Round`.
+///
+/// Edges are snapped at their absolute positions, so adjacent nodes stay adjacent and
+/// sizes are the distances between snapped edges.
+pub(crate) fn snap_layout(
+    tree: &mut impl RoundTree,
+    node_id: NodeId,
+    scale_factor: f32,
+    snapping: PixelSnapping,
+) {
+    let snap = |value: f32| snapping.snap(value, scale_factor);
+    snap_layout_inner(tree, node_id, 0.0, 0.0, &snap);
+}
+
+fn snap_layout_inner<F>(
+    tree: &mut impl RoundTree,
+    node_id: NodeId,
+    cumulative_x: f32,
+    cumulative_y: f32,
+    snap: &F,
+) where
+    F: Fn(f32) -> f32,
+{
+    let unrounded = *tree.get_unrounded_layout(node_id);
+    let cumulative_x = cumulative_x + unrounded.location.x;
+    let cumulative_y = cumulative_y + unrounded.location.y;
+    // the distance between two offsets from the snapped start of the node
+    let span_x = |from: f32, to: f32| snap(cumulative_x + to) - snap(cumulative_x + from);
+    let span_y = |from: f32, to: f32| snap(cumulative_y + to) - snap(cumulative_y + from);
+    let width = unrounded.size.width;
+    let height = unrounded.size.height;
+
+    let mut layout = unrounded;
+    layout.location.x = snap(unrounded.location.x);
+    layout.location.y = snap(unrounded.location.y);
+    layout.size.width = span_x(0.0, width);
+    layout.size.height = span_y(0.0, height);
+    layout.scrollbar_size.width = snap(unrounded.scrollbar_size.width);
+    layout.scrollbar_size.height = snap(unrounded.scrollbar_size.height);
+    layout.border.left = span_x(0.0, unrounded.border.left);
+    layout.border.right = span_x(width - unrounded.border.right, width);
+    layout.border.top = span_y(0.0, unrounded.border.top);
+    layout.border.bottom = span_y(height - unrounded.border.bottom, height);
+    layout.padding.left = span_x(0.0, unrounded.padding.left);
+    layout.padding.right = span_x(width - unrounded.padding.right, width);
+    layout.padding.top = span_y(0.0, unrounded.padding.top);
+    layout.padding.bottom = span_y(height - unrounded.padding.bottom, height);
+    #[cfg(feature = "content_size")]
+    {
+        layout.content_size.width = span_x(0.0, unrounded.content_size.width);
+        layout.content_size.height = span_y(0.0, unrounded.content_size.height);
+    }
+    tree.set_final_layout(node_id, &layout);
+
+    for index in 0..tree.child_count(node_id) {
+        let child_id = tree.get_child_id(node_id, index);
+        snap_layout_inner(tree, child_id, cumulative_x, cumulative_y, snap);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snaps_to_half_units_at_scale_two() {
+        assert_eq!(PixelSnapping::Round.snap(10.3, 2.0), 10.5);
+        assert_eq!(PixelSnapping::Round.snap(10.2, 2.0), 10.0);
+        assert_eq!(PixelSnapping::Round.snap(10.75, 2.0), 11.0);
+    }
+
+    #[test]
+    fn snaps_to_thirds_at_scale_three() {
+        assert_eq!(PixelSnapping::Round.snap(1.0, 3.0), 1.0);
+        assert_eq!(PixelSnapping::Round.snap(0.3, 3.0), 1.0 / 3.0);
+        assert_eq!(PixelSnapping::Round.snap(0.6, 3.0), 2.0 / 3.0);
+    }
+
+    #[test]
+    fn floor_and_ceil_snap_to_the_pixel_before_and_after() {
+        assert_eq!(PixelSnapping::Floor.snap(10.9, 1.0), 10.0);
+        assert_eq!(PixelSnapping::Ceil.snap(10.1, 1.0), 11.0);
+        assert_eq!(PixelSnapping::Floor.snap(10.4, 2.0), 10.0);
+        assert_eq!(PixelSnapping::Ceil.snap(10.1, 2.0), 10.5);
+    }
+
+    #[test]
+    fn values_on_the_grid_ignore_the_mode() {
+        assert_eq!(PixelSnapping::Floor.snap(2.0 / 3.0 - 1e-5, 3.0), 2.0 / 3.0);
+        assert_eq!(PixelSnapping::Ceil.snap(10.00001, 1.0), 10.0);
+    }
+
+    #[test]
+    fn invalid_scale_factors_snap_to_whole_units() {
+        for scale_factor in [0.0, -2.0, f32::NAN, f32::INFINITY] {
+            assert_eq!(PixelSnapping::Round.snap(10.3, scale_factor), 10.0);
+        }
+    }
+
+    #[test]
+    fn configs_are_ordered_by_scale_factor() {
+        use crate::impls::TaffyConfig;
+
+        let config = TaffyConfig {
+            scale_factor: f32::NAN,
+            ..TaffyConfig::default()
+        };
+        assert_eq!(config, config);
+        assert!(TaffyConfig::default() < TaffyConfig::default().with_scale_factor(2.0));
+    }
+
+    #[cfg(feature = "flexbox")]
+    mod layout {
+        use compose_rt::{Composer, Root};
+        use taffy::{AvailableSpace, Size};
+
+        use crate::impls::{Scope, TaffyConfig};
+        use crate::modifier::{modifier, StyleModifier};
+        use crate::test_utils::children;
+        use crate::widgets::{container, leaf};
+        use crate::{PixelSnapping, TaffyLayout};
+
+        fn layout_thirds(config: TaffyConfig) -> Vec<(f32, f32)> {
+            fn app(s: Scope<Root, ()>) {
+                container(s, modifier().size(100.0, 10.0), |s| {
+                    for _ in 0..3 {
+                        leaf(s, modifier().flex_grow(1.0));
+                    }
+                });
+            }
+            let mut recomposer = Composer::compose(app, config);
+            recomposer
+                .compute_layout(Size {
+                    width: AvailableSpace::Definite(100.0),
+                    height: AvailableSpace::Definite(10.0),
+                })
+                .unwrap();
+            children(&recomposer, recomposer.root_node_key())
+                .into_iter()
+                .map(|child| {
+                    let layout = recomposer.layout(child).unwrap();
+                    (layout.location.x, layout.size.width)
+                })
+                .collect()
+        }
+
+        #[test]
+        fn snapped_siblings_stay_adjacent() {
+            let config = TaffyConfig::default().with_scale_factor(2.0);
+            assert_eq!(
+                layout_thirds(config),
+                vec![(0.0, 33.5), (33.5, 33.0), (66.5, 33.5)]
+            );
+            let config = config.with_pixel_snapping(PixelSnapping::Floor);
+            assert_eq!(
+                layout_thirds(config),
+                vec![(0.0, 33.0), (33.0, 33.5), (66.5, 33.5)]
+            );
+        }
+
+        #[test]
+        fn zero_scale_factor_falls_back_to_one() {
+            let config = TaffyConfig::default().with_scale_factor(0.0);
+            assert_eq!(config.scale_factor, 1.0);
+            assert_eq!(
+                layout_thirds(config),
+                vec![(0.0, 33.0), (33.0, 34.0), (67.0, 33.0)]
+            );
+        }
+    }
+}
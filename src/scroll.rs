@@ -0,0 +1,255 @@
+use compose_rt::{Composer, NodeKey};
+use taffy::{CoreStyle, Overflow, Point};
+
+use crate::changes::StateUpdates;
+#[cfg(feature = "content_size")]
+use crate::geometry::selected_layout;
+use crate::geometry::{absolute_layout, layout_parent, node_data};
+use crate::traits::{TaffyConfig, TaffyNode};
+use crate::LayoutError;
+
+/// Returns whether content overflowing along an axis with this overflow can be scrolled.
+#[inline(always)]
+fn is_scrollable(overflow: Overflow) -> bool {
+    matches!(overflow, Overflow::Hidden | Overflow::Scroll)
+}
+
+/// The largest scroll offset of the node, zero along the axes which do not scroll.
+#[cfg_attr(not(feature = "content_size"), allow(unused_variables))]
+fn max_scroll_offset<T>(composer: &Composer<T>, data: &T) -> Point<f32>
+where
+    T: TaffyNode,
+    T::Context: TaffyConfig,
+{
+    let overflow = data.get_core_container_style().overflow();
+    #[cfg(feature = "content_size")]
+    {
+        let layout = selected_layout(composer, data);
+        Point {
+            x: if is_scrollable(overflow.x) {
+                layout.scroll_width()
+            } else {
+                0.0
+            },
+            y: if is_scrollable(overflow.y) {
+                layout.scroll_height()
+            } else {
+                0.0
+            },
+        }
+    }
+    // the content size is needed to know how far the content overflows
+    #[cfg(not(feature = "content_size"))]
+    Point::ZERO
+}
+
+/// Clamps the offset between zero and the largest scroll offset of the node, returns the
+/// offset which was applied.
+pub(crate) fn set_scroll_offset<T>(
+    composer: &mut Composer<T>,
+    node_key: NodeKey,
+    offset: Point<f32>,
+) -> Result<Point<f32>, LayoutError>
+where
+    T: TaffyNode,
+    T::Context: TaffyConfig,
+{
+    let max = max_scroll_offset(composer, node_data(composer, node_key)?);
+    let offset = Point {
+        x: offset.x.clamp(0.0, max.x),
+        y: offset.y.clamp(0.0, max.y),
+    };
+    if let Some(data) = composer.nodes[node_key].data.as_mut() {
+        data.set_scroll_offset(offset);
+    }
+    Ok(offset)
+}
+
+/// Clamps the scroll offsets of the nodes again after their layout changed.
+pub(crate) fn clamp_scroll_offsets<T>(composer: &mut Composer<T>, node_keys: &[NodeKey])
+where
+    T: TaffyNode,
+    T::Context: TaffyConfig,
+{
+    for &node_key in node_keys {
+        let Ok(data) = node_data(composer, node_key) else {
+            continue;
+        };
+        let offset = data.get_scroll_offset();
+        if offset != Point::ZERO {
+            let _ = set_scroll_offset(composer, node_key, offset);
+        }
+    }
+}
+
+/// Scrolls every scrollable ancestor of the node by the least amount which brings the
+/// border box of the node into its padding box, returns the scrolled ancestors.
+pub(crate) fn scroll_to<T>(
+    composer: &mut Composer<T>,
+    node_key: NodeKey,
+) -> Result<Vec<NodeKey>, LayoutError>
+where
+    T: TaffyNode,
+    T::Context: TaffyConfig,
+{
+    node_data(composer, node_key)?;
+    let mut scrolled = Vec::new();
+    let mut current = node_key;
+    while let Some(parent) = layout_parent(composer, current) {
+        let Ok(parent_data) = node_data(composer, parent) else {
+            break;
+        };
+        let overflow = parent_data.get_core_container_style().overflow();
+        if is_scrollable(overflow.x) || is_scrollable(overflow.y) {
+            let target = absolute_layout(composer, node_key)?.border_box();
+            let view = absolute_layout(composer, parent)?.padding_box();
+            let offset = parent_data.get_scroll_offset();
+            let delta = Point {
+                x: reveal(target.left, target.right, view.left, view.right),
+                y: reveal(target.top, target.bottom, view.top, view.bottom),
+            };
+            set_scroll_offset(
+                composer,
+                parent,
+                Point {
+                    x: offset.x + delta.x,
+                    y: offset.y + delta.y,
+                },
+            )?;
+            scrolled.push(parent);
+        }
+        current = parent;
+    }
+    Ok(scrolled)
+}
+
+/// The scroll delta along one axis which moves `start..end` into `view_start..view_end`,
+/// aligning the start when the target is larger than the view.
+#[inline(always)]
+fn reveal(start: f32, end: f32, view_start: f32, view_end: f32) -> f32 {
+    if start < view_start {
+        start - view_start
+    } else if end > view_end {
+        (end - view_end).min(start - view_start)
+    } else {
+        0.0
+    }
+}
+
+/// Returns the scroll offset states of the nodes together with their current offsets,
+/// to be published once the composer is released.
+pub(crate) fn scroll_offset_states<T>(
+    composer: &Composer<T>,
+    node_keys: &[NodeKey],
+) -> StateUpdates<Point<f32>, T>
+where
+    T: TaffyNode,
+{
+    node_keys
+        .iter()
+        .filter_map(|&node_key| {
+            let data = composer.nodes.get(node_key)?.data.as_ref()?;
+            Some((data.get_scroll_offset_state()?, data.get_scroll_offset()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reveal_keeps_visible_targets() {
+        assert_eq!(reveal(10.0, 20.0, 0.0, 50.0), 0.0);
+        assert_eq!(reveal(0.0, 50.0, 0.0, 50.0), 0.0);
+    }
+
+    #[test]
+    fn reveal_scrolls_back_to_targets_before_the_view() {
+        assert_eq!(reveal(-30.0, -10.0, 0.0, 50.0), -30.0);
+        assert_eq!(reveal(-10.0, 10.0, 0.0, 50.0), -10.0);
+    }
+
+    #[test]
+    fn reveal_scrolls_forward_to_targets_after_the_view() {
+        assert_eq!(reveal(60.0, 80.0, 0.0, 50.0), 30.0);
+        assert_eq!(reveal(40.0, 60.0, 0.0, 50.0), 10.0);
+    }
+
+    #[test]
+    fn reveal_aligns_the_start_of_targets_larger_than_the_view() {
+        assert_eq!(reveal(20.0, 120.0, 0.0, 50.0), 20.0);
+        assert_eq!(reveal(-20.0, 80.0, 0.0, 50.0), -20.0);
+    }
+
+    #[cfg(all(feature = "flexbox", feature = "content_size"))]
+    mod layout {
+        use compose_rt::{NodeKey, Recomposer, Root};
+        use taffy::Point;
+
+        use crate::impls::{LayoutNode, Scope};
+        use crate::modifier::{modifier, StyleModifier};
+        use crate::test_utils::{children, compose_layout, point, SPACE};
+        use crate::widgets::{column, leaf, scroll};
+        use crate::TaffyLayout;
+
+        /// A 50x50 scroll container around a column of ten 50x20 leaves.
+        fn app(s: Scope<Root, ()>) {
+            scroll(s, modifier().size(50.0, 50.0), |s| {
+                column(s, modifier().width(50.0).flex_shrink(0.0), |s| {
+                    for index in 0..10 {
+                        s.key(index, |s| {
+                            leaf(s, modifier().size(50.0, 20.0).flex_shrink(0.0));
+                        });
+                    }
+                });
+            });
+        }
+
+        fn compose() -> (Recomposer<(), LayoutNode<()>>, NodeKey, Vec<NodeKey>) {
+            let recomposer = compose_layout(app);
+            let root = recomposer.root_node_key();
+            let content = children(&recomposer, root)[0];
+            let leaves = children(&recomposer, content);
+            (recomposer, root, leaves)
+        }
+
+        #[test]
+        fn offsets_are_clamped_to_the_overflow() {
+            let (mut recomposer, root, _) = compose();
+            let offset = recomposer
+                .set_scroll_offset(root, point(30.0, 500.0))
+                .unwrap();
+            assert_eq!(offset, point(0.0, 150.0));
+            let offset = recomposer.scroll_by(root, point(0.0, -200.0)).unwrap();
+            assert_eq!(offset, Point::ZERO);
+        }
+
+        #[test]
+        fn offsets_are_clamped_again_when_the_content_shrinks() {
+            let (mut recomposer, root, leaves) = compose();
+            recomposer
+                .set_scroll_offset(root, point(0.0, 150.0))
+                .unwrap();
+            recomposer.with_composer_mut(|composer| {
+                for &leaf_key in &leaves[4..] {
+                    let data = composer.nodes[leaf_key].data.as_mut().unwrap();
+                    data.set_style(modifier().size(50.0, 0.0).flex_shrink(0.0));
+                }
+            });
+            recomposer.compute_layout(SPACE).unwrap();
+            assert_eq!(recomposer.scroll_offset(root).unwrap(), point(0.0, 30.0));
+        }
+
+        #[test]
+        fn scroll_to_reveals_the_node() {
+            let (mut recomposer, root, leaves) = compose();
+            recomposer.scroll_to(leaves[6]).unwrap();
+            assert_eq!(recomposer.scroll_offset(root).unwrap(), point(0.0, 90.0));
+            recomposer.scroll_to(leaves[5]).unwrap();
+            assert_eq!(recomposer.scroll_offset(root).unwrap(), point(0.0, 90.0));
+            recomposer.scroll_to(leaves[1]).unwrap();
+            assert_eq!(recomposer.scroll_offset(root).unwrap(), point(0.0, 20.0));
+        }
+    }
+}
@@ -0,0 +1,185 @@
+use std::fmt::{self, Display, Formatter};
+use std::path::Path;
+use std::{env, fs};
+
+use compose_rt::NodeKey;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use taffy::{Point, PrintTree, Rect, Size, TraversePartialTree};
+
+use crate::traits::{IntoNodeId, IntoNodeKey, TaffyConfig, TaffyNode};
+use crate::TaffyTreeView;
+
+/// Environment variable which makes [`assert_layout_snapshot`] overwrite the stored snapshots.
+pub const UPDATE_SNAPSHOTS_ENV: &str = "UPDATE_LAYOUT_SNAPSHOTS";
+
+/// A stable, diff-friendly copy of a computed layout subtree.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LayoutSnapshot {
+    /// The call site and key of the scope which created the node, with `/` separated paths
+    pub scope: String,
+    /// The same label as `PrintTree::get_debug_label`
+    pub label: String,
+    pub location: Point<f32>,
+    pub size: Size<f32>,
+    #[cfg(feature = "content_size")]
+    pub content_size: Size<f32>,
+    pub border: Rect<f32>,
+    pub padding: Rect<f32>,
+    pub children: Vec<LayoutSnapshot>,
+}
+
+impl LayoutSnapshot {
+    /// Captures the final layout of the subtree, as selected by `TaffyConfig::use_rounding`.
+    pub fn new<T>(tree: &TaffyTreeView<'_, T>, node_key: NodeKey) -> Self
+    where
+        T: TaffyNode,
+        T::Context: TaffyConfig,
+    {
+        let node_id = node_key.into_node_id();
+        let layout = tree.get_final_layout(node_id);
+        Self {
+            scope: scope_label(&format!("{:?}", tree.composer.nodes[node_key].scope_id)),
+            label: tree.get_debug_label(node_id).to_string(),
+            location: layout.location,
+            size: layout.size,
+            #[cfg(feature = "content_size")]
+            content_size: layout.content_size,
+            border: layout.border,
+            padding: layout.padding,
+            children: tree
+                .child_ids(node_id)
+                .map(|child_id| Self::new(tree, child_id.into_node_key()))
+                .collect(),
+        }
+    }
+
+    fn fmt_node(&self, f: &mut Formatter<'_>, depth: usize) -> fmt::Result {
+        write!(
+            f,
+            "{indent}{label} @ {scope} [x: {x} y: {y} w: {w} h: {h}",
+            indent = "  ".repeat(depth),
+            label = self.label,
+            scope = self.scope,
+            x = self.location.x,
+            y = self.location.y,
+            w = self.size.width,
+            h = self.size.height,
+        )?;
+        #[cfg(feature = "content_size")]
+        write!(
+            f,
+            " content_w: {} content_h: {}",
+            self.content_size.width, self.content_size.height
+        )?;
+        writeln!(
+            f,
+            " border: l:{} r:{} t:{} b:{} padding: l:{} r:{} t:{} b:{}]",
+            self.border.left,
+            self.border.right,
+            self.border.top,
+            self.border.bottom,
+            self.padding.left,
+            self.padding.right,
+            self.padding.top,
+            self.padding.bottom,
+        )?;
+        for child in &self.children {
+            child.fmt_node(f, depth + 1)?;
+        }
+        Ok(())
+    }
+}
+
+/// Uses `/` as the path separator, so snapshots written on Windows match other platforms.
+fn scope_label(scope_id: &str) -> String {
+    scope_id.replace('\\', "/")
+}
+
+/// One line per node, children indented by two spaces below their parent.
+impl Display for LayoutSnapshot {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_node(f, 0)
+    }
+}
+
+/// Compares the snapshot against the one stored at `path`.
+///
+/// The snapshot file is written instead when it does not exist yet or when the
+/// [`UPDATE_SNAPSHOTS_ENV`] environment variable is set.
+///
+/// # Panics
+///
+/// Panics with a line diff if the snapshots differ or the file cannot be accessed.
+#[track_caller]
+pub fn assert_layout_snapshot(snapshot: &LayoutSnapshot, path: impl AsRef<Path>) {
+    let path = path.as_ref();
+    let actual = snapshot.to_string();
+    if env::var_os(UPDATE_SNAPSHOTS_ENV).is_some() || !path.exists() {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .unwrap_or_else(|err| panic!("failed to create {}: {err}", dir.display()));
+        }
+        fs::write(path, &actual)
+            .unwrap_or_else(|err| panic!("failed to write {}: {err}", path.display()));
+        return;
+    }
+    let expected = fs::read_to_string(path)
+        .unwrap_or_else(|err| panic!("failed to read {}: {err}", path.display()));
+    if expected != actual {
+        panic!(
+            "layout snapshot {} does not match, set {UPDATE_SNAPSHOTS_ENV}=1 to update it\n{}",
+            path.display(),
+            line_diff(&expected, &actual)
+        );
+    }
+}
+
+fn line_diff(expected: &str, actual: &str) -> String {
+    let expected = expected.lines().collect::<Vec<_>>();
+    let actual = actual.lines().collect::<Vec<_>>();
+    let mut diff = String::new();
+    for idx in 0..expected.len().max(actual.len()) {
+        match (expected.get(idx), actual.get(idx)) {
+            (Some(e), Some(a)) if e == a => {
+                diff.push_str("  ");
+                diff.push_str(e);
+                diff.push('\n');
+            }
+            (e, a) => {
+                if let Some(e) = e {
+                    diff.push_str("- ");
+                    diff.push_str(e);
+                    diff.push('\n');
+                }
+                if let Some(a) = a {
+                    diff.push_str("+ ");
+                    diff.push_str(a);
+                    diff.push('\n');
+                }
+            }
+        }
+    }
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scope_label_uses_forward_slashes() {
+        assert_eq!(
+            scope_label("src\\ui\\app.rs:10:5|0"),
+            "src/ui/app.rs:10:5|0"
+        );
+        assert_eq!(scope_label("src/ui/app.rs:10:5|0"), "src/ui/app.rs:10:5|0");
+    }
+
+    #[test]
+    fn line_diff_marks_changed_lines() {
+        let diff = line_diff("a\nb\nc", "a\nx\nc\nd");
+        assert_eq!(diff, "  a\n- b\n+ x\n  c\n+ d\n");
+    }
+}
@@ -1,3 +1,7 @@
+#[cfg(feature = "rayon")]
+use std::collections::HashMap;
+use std::collections::HashSet;
+
 use compose_rt::{Composer, NodeKey};
 #[cfg(feature = "block_layout")]
 use taffy::{compute::compute_block_layout, LayoutBlockContainer};
@@ -7,18 +11,58 @@ use taffy::{compute::compute_flexbox_layout, LayoutFlexboxContainer};
 use taffy::{compute::compute_grid_layout, LayoutGridContainer};
 use taffy::{
     compute_cached_layout, compute_hidden_layout, compute_leaf_layout, AvailableSpace, CacheTree,
-    Display, FlexDirection, FlexboxContainerStyle, Layout, LayoutPartialTree, NodeId, PrintTree,
-    RoundTree, RunMode, Size, TraversePartialTree, TraverseTree,
+    Display, Layout, LayoutPartialTree, NodeId, PrintTree, RoundTree, RunMode, Size,
+    TraversePartialTree, TraverseTree,
 };
-
+#[cfg(feature = "detailed_layout_info")]
+use taffy::{DetailedGridInfo, DetailedLayoutInfo};
+#[cfg(feature = "flexbox")]
+use taffy::{FlexDirection, FlexboxContainerStyle};
+#[cfg(feature = "rayon")]
+use taffy::{LayoutInput, LayoutOutput};
+
+use crate::changes::StateUpdates;
+use crate::geometry::{is_layout_child, layout_children};
+use crate::layout::resolve_display;
+#[cfg(feature = "rayon")]
+use crate::parallel::SubtreeLayout;
 use crate::traits::{IntoNodeId, IntoNodeKey, TaffyConfig, TaffyNode};
 
-pub struct TaffyTreeChildIter<'a>(core::slice::Iter<'a, NodeKey>);
-impl Iterator for TaffyTreeChildIter<'_> {
+pub struct TaffyTreeChildIter<'a, T>
+where
+    T: TaffyNode,
+{
+    composer: &'a Composer<T>,
+    children: core::slice::Iter<'a, NodeKey>,
+}
+
+impl<'a, T> TaffyTreeChildIter<'a, T>
+where
+    T: TaffyNode,
+{
+    /// Iterates the children laid out inside the node, overlays are separate layout roots.
+    #[inline(always)]
+    fn new(composer: &'a Composer<T>, node_key: NodeKey) -> Self {
+        Self {
+            composer,
+            children: composer.nodes[node_key].children.iter(),
+        }
+    }
+}
+
+impl<T> Iterator for TaffyTreeChildIter<'_, T>
+where
+    T: TaffyNode,
+{
     type Item = NodeId;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.0.next().copied().map(IntoNodeId::into_node_id)
+        let composer = self.composer;
+        self.children
+            .by_ref()
+            .copied()
+            .find(|&child_key| is_layout_child(composer, child_key))
+            .map(IntoNodeId::into_node_id)
     }
 }
 
@@ -46,26 +90,29 @@ where
     T::Context: TaffyConfig,
 {
     type ChildIter<'a>
-        = TaffyTreeChildIter<'a>
+        = TaffyTreeChildIter<'a, T>
     where
         Self: 'a;
 
     #[inline(always)]
     fn child_ids(&self, node_id: NodeId) -> Self::ChildIter<'_> {
         let node_key = node_id.into_node_key();
-        TaffyTreeChildIter(self.composer.nodes[node_key].children.iter())
+        TaffyTreeChildIter::new(self.composer, node_key)
     }
 
     #[inline(always)]
     fn child_count(&self, node_id: NodeId) -> usize {
         let node_key = node_id.into_node_key();
-        self.composer.nodes[node_key].children.len()
+        layout_children(self.composer, node_key).count()
     }
 
     #[inline(always)]
     fn get_child_id(&self, node_id: NodeId, child_index: usize) -> NodeId {
         let node_key = node_id.into_node_key();
-        self.composer.nodes[node_key].children[child_index].into_node_id()
+        layout_children(self.composer, node_key)
+            .nth(child_index)
+            .expect("child index out of range")
+            .into_node_id()
     }
 }
 
@@ -87,21 +134,7 @@ where
             .data
             .as_ref()
             .unwrap();
-        let display = node.get_display();
-        let num_children = self.child_count(node_id);
-        match (num_children, display) {
-            (_, Display::None) => "NONE",
-            (0, _) => "LEAF",
-            #[cfg(feature = "block_layout")]
-            (_, Display::Block) => "BLOCK",
-            #[cfg(feature = "flexbox")]
-            (_, Display::Flex) => match node.get_flexbox_container_style().flex_direction() {
-                FlexDirection::Row | FlexDirection::RowReverse => "FLEX ROW",
-                FlexDirection::Column | FlexDirection::ColumnReverse => "FLEX COL",
-            },
-            #[cfg(feature = "grid")]
-            (_, Display::Grid) => "GRID",
-        }
+        debug_label(node, self.child_count(node_id), &self.composer.context)
     }
 
     #[inline(always)]
@@ -122,6 +155,35 @@ where
     }
 }
 
+#[allow(unreachable_patterns)]
+fn debug_label<T>(node: &T, num_children: usize, context: &T::Context) -> &'static str
+where
+    T: TaffyNode,
+    T::Context: TaffyConfig,
+{
+    let Some(display) = resolve_display(node.get_display(), context.display_fallback()) else {
+        return if num_children == 0 {
+            "LEAF"
+        } else {
+            "DISABLED"
+        };
+    };
+    match (num_children, display) {
+        (_, Display::None) => "NONE",
+        (0, _) => "LEAF",
+        #[cfg(feature = "block_layout")]
+        (_, Display::Block) => "BLOCK",
+        #[cfg(feature = "flexbox")]
+        (_, Display::Flex) => match node.get_flexbox_container_style().flex_direction() {
+            FlexDirection::Row | FlexDirection::RowReverse => "FLEX ROW",
+            FlexDirection::Column | FlexDirection::ColumnReverse => "FLEX COL",
+        },
+        #[cfg(feature = "grid")]
+        (_, Display::Grid) => "GRID",
+        _ => "DISABLED",
+    }
+}
+
 pub struct TaffyTree<'a, T, M>
 where
     T: TaffyNode,
@@ -136,6 +198,15 @@ where
 {
     pub composer: &'a mut Composer<T>,
     pub measure_function: M,
+    /// The selected layouts of the nodes laid out in this pass, as they were before
+    /// the pass first touched them
+    pub(crate) previous_layouts: Vec<(NodeKey, Layout)>,
+    /// The space available to the nodes observing it, see `TaffyNode::get_constraints_state`
+    pub(crate) constraints: StateUpdates<Size<AvailableSpace>, T>,
+    touched: HashSet<NodeKey>,
+    /// The subtrees laid out in parallel ahead of the pass, by their root
+    #[cfg(feature = "rayon")]
+    pub(crate) subtrees: HashMap<NodeKey, SubtreeLayout>,
 }
 
 impl<'a, T, M> TaffyTree<'a, T, M>
@@ -154,8 +225,56 @@ where
         Self {
             composer,
             measure_function,
+            previous_layouts: Vec::new(),
+            constraints: Vec::new(),
+            touched: HashSet::new(),
+            #[cfg(feature = "rayon")]
+            subtrees: HashMap::new(),
         }
     }
+
+    /// Returns the data of the node, recording its layout the first time it is changed.
+    #[inline(always)]
+    fn touch_data_mut(&mut self, node_key: NodeKey) -> &mut T {
+        let use_rounding = self.composer.context.use_rounding();
+        let data = self.composer.nodes[node_key].data.as_mut().unwrap();
+        if self.touched.insert(node_key) {
+            let layout = if use_rounding {
+                data.get_final_layout()
+            } else {
+                data.get_unrounded_layout()
+            };
+            self.previous_layouts.push((node_key, *layout));
+        }
+        data
+    }
+
+    /// Forgets the grid tracks of the node, they are recorded again when the node is laid
+    /// out as a grid.
+    #[cfg(feature = "detailed_layout_info")]
+    #[inline(always)]
+    fn clear_detailed_layout_info(&mut self, node_key: NodeKey) {
+        self.composer.nodes[node_key]
+            .data
+            .as_mut()
+            .unwrap()
+            .set_detailed_layout_info(DetailedLayoutInfo::None);
+    }
+
+    /// Returns the output of a subtree laid out in parallel when it was laid out with the
+    /// same size, merging its layouts when the pass performs layout.
+    #[cfg(feature = "rayon")]
+    fn merge_subtree(&mut self, node_key: NodeKey, inputs: &LayoutInput) -> Option<LayoutOutput> {
+        let subtree = self.subtrees.remove(&node_key)?;
+        let output = subtree.fits(inputs).then(|| {
+            if inputs.run_mode == RunMode::PerformLayout {
+                subtree.merge(self);
+            }
+            subtree.output()
+        });
+        self.subtrees.insert(node_key, subtree);
+        output
+    }
 }
 
 impl<T, M> TraversePartialTree for TaffyTree<'_, T, M>
@@ -171,26 +290,29 @@ where
     ) -> Size<f32>,
 {
     type ChildIter<'a>
-        = TaffyTreeChildIter<'a>
+        = TaffyTreeChildIter<'a, T>
     where
         Self: 'a;
 
     #[inline(always)]
     fn child_ids(&self, node_id: NodeId) -> Self::ChildIter<'_> {
         let node_key = node_id.into_node_key();
-        TaffyTreeChildIter(self.composer.nodes[node_key].children.iter())
+        TaffyTreeChildIter::new(self.composer, node_key)
     }
 
     #[inline(always)]
     fn child_count(&self, node_id: NodeId) -> usize {
         let node_key = node_id.into_node_key();
-        self.composer.nodes[node_key].children.len()
+        layout_children(self.composer, node_key).count()
     }
 
     #[inline(always)]
     fn get_child_id(&self, node_id: NodeId, child_index: usize) -> NodeId {
         let node_key = node_id.into_node_key();
-        self.composer.nodes[node_key].children[child_index].into_node_id()
+        layout_children(self.composer, node_key)
+            .nth(child_index)
+            .expect("child index out of range")
+            .into_node_id()
     }
 }
 
@@ -285,22 +407,7 @@ where
             .data
             .as_ref()
             .unwrap();
-        let display = node.get_display();
-        let num_children = self.child_count(node_id);
-
-        match (num_children, display) {
-            (_, Display::None) => "NONE",
-            (0, _) => "LEAF",
-            #[cfg(feature = "block_layout")]
-            (_, Display::Block) => "BLOCK",
-            #[cfg(feature = "flexbox")]
-            (_, Display::Flex) => match node.get_flexbox_container_style().flex_direction() {
-                FlexDirection::Row | FlexDirection::RowReverse => "FLEX ROW",
-                FlexDirection::Column | FlexDirection::ColumnReverse => "FLEX COL",
-            },
-            #[cfg(feature = "grid")]
-            (_, Display::Grid) => "GRID",
-        }
+        debug_label(node, self.child_count(node_id), &self.composer.context)
     }
 
     #[inline(always)]
@@ -349,17 +456,13 @@ where
 
     #[inline(always)]
     fn set_unrounded_layout(&mut self, node_id: NodeId, layout: &Layout) {
-        self.composer
-            .nodes
-            .get_mut(node_id.into_node_key())
-            .unwrap()
-            .data
-            .as_mut()
-            .unwrap()
-            .set_unrounded_layout(layout);
+        let data = self.touch_data_mut(node_id.into_node_key());
+        data.set_unrounded_layout(layout);
+        data.set_root_available_space(None);
     }
 
     #[inline(always)]
+    #[allow(unreachable_patterns)]
     fn compute_child_layout(
         &mut self,
         node_id: NodeId,
@@ -368,9 +471,21 @@ where
         // If RunMode is PerformHiddenLayout then this indicates that an ancestor node is `Display::None`
         // and thus that we should lay out this node using hidden layout regardless of it's own display style.
         if inputs.run_mode == RunMode::PerformHiddenLayout {
+            #[cfg(feature = "detailed_layout_info")]
+            self.clear_detailed_layout_info(node_id.into_node_key());
             return compute_hidden_layout(self, node_id);
         }
 
+        if inputs.run_mode == RunMode::PerformLayout {
+            let data = self.composer.nodes[node_id.into_node_key()]
+                .data
+                .as_ref()
+                .unwrap();
+            if let Some(state) = data.get_constraints_state() {
+                self.constraints.push((state, inputs.available_space));
+            }
+        }
+
         // We run the following wrapped in "compute_cached_layout", which will check the cache for an entry matching the node and inputs and:
         //   - Return that entry if exists
         //   - Else call the passed closure (below) to compute the result
@@ -378,22 +493,34 @@ where
         // If there was no cache match and a new result needs to be computed then that result will be added to the cache
         compute_cached_layout(self, node_id, inputs, |tree, node_id, inputs| {
             let node_key = node_id.into_node_key();
+            #[cfg(feature = "rayon")]
+            if let Some(output) = tree.merge_subtree(node_key, &inputs) {
+                return output;
+            }
+            #[cfg(feature = "detailed_layout_info")]
+            if inputs.run_mode == RunMode::PerformLayout {
+                tree.clear_detailed_layout_info(node_key);
+            }
             let display_mode = tree.composer.nodes[node_key]
                 .data
                 .as_ref()
                 .unwrap()
                 .get_display();
+            let fallback = tree.composer.context.display_fallback();
+            // the subtree is validated before layout, so only leaves can have an unresolved
+            // display mode, and they are measured like any other leaf
+            let display_mode = resolve_display(display_mode, fallback);
             let has_children = tree.child_count(node_id) > 0;
 
             // Dispatch to a layout algorithm based on the node's display style and whether the node has children or not.
             match (display_mode, has_children) {
-                (Display::None, _) => compute_hidden_layout(tree, node_id),
+                (Some(Display::None), _) => compute_hidden_layout(tree, node_id),
                 #[cfg(feature = "block_layout")]
-                (Display::Block, true) => compute_block_layout(tree, node_id, inputs),
+                (Some(Display::Block), true) => compute_block_layout(tree, node_id, inputs),
                 #[cfg(feature = "flexbox")]
-                (Display::Flex, true) => compute_flexbox_layout(tree, node_id, inputs),
+                (Some(Display::Flex), true) => compute_flexbox_layout(tree, node_id, inputs),
                 #[cfg(feature = "grid")]
-                (Display::Grid, true) => compute_grid_layout(tree, node_id, inputs),
+                (Some(Display::Grid), true) => compute_grid_layout(tree, node_id, inputs),
                 (_, false) => {
                     let data = tree
                         .composer
@@ -415,6 +542,7 @@ where
                     };
                     compute_leaf_layout(inputs, style, measure_function)
                 }
+                (_, true) => compute_hidden_layout(tree, node_id),
             }
         })
     }
@@ -544,6 +672,16 @@ where
             .unwrap()
             .get_grid_item_style()
     }
+
+    #[cfg(feature = "detailed_layout_info")]
+    #[inline(always)]
+    fn set_detailed_grid_info(&mut self, node_id: NodeId, detailed_grid_info: DetailedGridInfo) {
+        self.composer.nodes[node_id.into_node_key()]
+            .data
+            .as_mut()
+            .unwrap()
+            .set_detailed_layout_info(DetailedLayoutInfo::Grid(Box::new(detailed_grid_info)));
+    }
 }
 
 impl<T, M> RoundTree for TaffyTree<'_, T, M>
@@ -569,13 +707,8 @@ where
 
     #[inline(always)]
     fn set_final_layout(&mut self, node_id: NodeId, layout: &Layout) {
-        self.composer
-            .nodes
-            .get_mut(node_id.into_node_key())
-            .unwrap()
-            .data
-            .as_mut()
-            .unwrap()
-            .set_final_layout(layout);
+        let data = self.touch_data_mut(node_id.into_node_key());
+        data.set_final_layout(layout);
+        data.set_root_available_space(None);
     }
 }
@@ -0,0 +1,32 @@
+use compose_rt::{Composer, NodeKey, Recomposer, Root};
+use taffy::{AvailableSpace, Point, Size};
+
+use crate::impls::{LayoutNode, Scope, TaffyConfig};
+use crate::traits::TaffyNode;
+use crate::TaffyLayout;
+
+/// The space the tests lay their apps out in.
+pub(crate) const SPACE: Size<AvailableSpace> = Size {
+    width: AvailableSpace::Definite(100.0),
+    height: AvailableSpace::Definite(100.0),
+};
+
+/// Composes the app with the default config and lays it out in `SPACE`.
+pub(crate) fn compose_layout(app: fn(Scope<Root, ()>)) -> Recomposer<(), LayoutNode<()>> {
+    let mut recomposer = Composer::compose(app, TaffyConfig::default());
+    recomposer.compute_layout(SPACE).unwrap();
+    recomposer
+}
+
+pub(crate) fn children<S, T>(recomposer: &Recomposer<S, T>, node_key: NodeKey) -> Vec<NodeKey>
+where
+    S: 'static,
+    T: TaffyNode,
+{
+    recomposer.with_composer(|composer| composer.nodes[node_key].children.clone())
+}
+
+#[inline(always)]
+pub(crate) fn point(x: f32, y: f32) -> Point<f32> {
+    Point { x, y }
+}
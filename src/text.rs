@@ -0,0 +1,357 @@
+use taffy::{AvailableSpace, NodeId, Size, Style};
+
+use crate::impls::{LayoutNode, Scope};
+use crate::widgets::place_in_parent;
+
+/// The font properties used to measure a text leaf.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextStyle {
+    /// The font size in layout units
+    pub font_size: f32,
+    /// The height of a line as a multiple of the font size
+    pub line_height: f32,
+}
+
+impl Default for TextStyle {
+    fn default() -> Self {
+        Self {
+            font_size: 16.0,
+            line_height: 1.2,
+        }
+    }
+}
+
+impl TextStyle {
+    #[inline(always)]
+    pub fn new(font_size: f32) -> Self {
+        Self {
+            font_size,
+            ..Default::default()
+        }
+    }
+
+    #[inline(always)]
+    pub fn with_line_height(mut self, line_height: f32) -> Self {
+        self.line_height = line_height;
+        self
+    }
+
+    /// The height of a line in layout units
+    #[inline(always)]
+    pub fn line_height_px(&self) -> f32 {
+        self.font_size * self.line_height
+    }
+}
+
+/// A text shaping backend which measures the size of a block of text.
+pub trait TextMeasurer {
+    /// Returns the size of the text wrapped to `max_width`, or of the text with
+    /// only explicit line breaks when `max_width` is `None`.
+    fn measure(&mut self, text: &str, style: &TextStyle, max_width: Option<f32>) -> Size<f32>;
+}
+
+/// Measures text assuming every character has the same advance, wrapping at whitespace.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MonospaceMeasurer {
+    /// The advance of a character as a multiple of the font size
+    pub advance: f32,
+}
+
+impl Default for MonospaceMeasurer {
+    fn default() -> Self {
+        Self { advance: 0.6 }
+    }
+}
+
+impl MonospaceMeasurer {
+    #[inline(always)]
+    pub fn new(advance: f32) -> Self {
+        Self { advance }
+    }
+}
+
+impl TextMeasurer for MonospaceMeasurer {
+    fn measure(&mut self, text: &str, style: &TextStyle, max_width: Option<f32>) -> Size<f32> {
+        let char_width = style.font_size * self.advance;
+        let mut width: f32 = 0.0;
+        let mut lines = 0;
+        for paragraph in text.split('\n') {
+            lines += 1;
+            let mut line_width = 0.0;
+            for word in paragraph.split_whitespace() {
+                let word_width = word.chars().count() as f32 * char_width;
+                if line_width == 0.0 {
+                    line_width = word_width;
+                } else if max_width.is_some_and(|max| line_width + char_width + word_width > max) {
+                    // words longer than the line overflow instead of being broken
+                    width = width.max(line_width);
+                    lines += 1;
+                    line_width = word_width;
+                } else {
+                    line_width += char_width + word_width;
+                }
+            }
+            width = width.max(line_width);
+        }
+        Size {
+            width,
+            height: lines as f32 * style.line_height_px(),
+        }
+    }
+}
+
+#[cfg(feature = "cosmic-text")]
+pub use cosmic::CosmicTextMeasurer;
+
+#[cfg(feature = "cosmic-text")]
+mod cosmic {
+    use cosmic_text::{Attrs, Buffer, FontSystem, Metrics, Shaping};
+    use taffy::Size;
+
+    use super::{TextMeasurer, TextStyle};
+
+    /// Measures text by shaping it with `cosmic-text`.
+    pub struct CosmicTextMeasurer {
+        pub font_system: FontSystem,
+        buffer: Option<Buffer>,
+    }
+
+    impl Default for CosmicTextMeasurer {
+        fn default() -> Self {
+            Self::new(FontSystem::new())
+        }
+    }
+
+    impl CosmicTextMeasurer {
+        #[inline(always)]
+        pub fn new(font_system: FontSystem) -> Self {
+            Self {
+                font_system,
+                buffer: None,
+            }
+        }
+    }
+
+    impl TextMeasurer for CosmicTextMeasurer {
+        fn measure(&mut self, text: &str, style: &TextStyle, max_width: Option<f32>) -> Size<f32> {
+            let metrics = Metrics::new(style.font_size, style.line_height_px());
+            let buffer = self
+                .buffer
+                .get_or_insert_with(|| Buffer::new_empty(metrics));
+            buffer.set_metrics(metrics);
+            buffer.set_size(max_width, None);
+            buffer.set_text(text, &Attrs::new(), Shaping::Advanced, None);
+            buffer.shape_until_scroll(&mut self.font_system, false);
+            buffer.layout_runs().fold(Size::ZERO, |size, run| Size {
+                width: size.width.max(run.line_w),
+                height: size.height + run.line_height,
+            })
+        }
+    }
+}
+
+const MEASURE_CACHE_SIZE: usize = 4;
+
+/// The node context of a text leaf, caching the measured sizes until the text or style changes.
+#[derive(Debug, Clone)]
+pub struct TextContext {
+    text: String,
+    style: TextStyle,
+    cache: Vec<(Option<f32>, Size<f32>)>,
+}
+
+impl TextContext {
+    #[inline(always)]
+    pub fn new(text: impl Into<String>, style: TextStyle) -> Self {
+        Self {
+            text: text.into(),
+            style,
+            cache: Vec::with_capacity(MEASURE_CACHE_SIZE),
+        }
+    }
+
+    #[inline(always)]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    #[inline(always)]
+    pub fn style(&self) -> &TextStyle {
+        &self.style
+    }
+
+    /// Replaces the text, returns whether it changed.
+    pub fn set_text(&mut self, text: impl Into<String>) -> bool {
+        let text = text.into();
+        if self.text == text {
+            return false;
+        }
+        self.text = text;
+        self.cache.clear();
+        true
+    }
+
+    /// Replaces the style, returns whether it changed.
+    pub fn set_style(&mut self, style: TextStyle) -> bool {
+        if self.style == style {
+            return false;
+        }
+        self.style = style;
+        self.cache.clear();
+        true
+    }
+
+    /// Measures the text for the given layout inputs.
+    ///
+    /// A known width is used as the wrapping width, `MinContent` wraps at every
+    /// opportunity and `MaxContent` only at explicit line breaks.
+    pub fn measure<M>(
+        &mut self,
+        measurer: &mut M,
+        known_dimensions: Size<Option<f32>>,
+        available_space: Size<AvailableSpace>,
+    ) -> Size<f32>
+    where
+        M: TextMeasurer + ?Sized,
+    {
+        if let Size {
+            width: Some(width),
+            height: Some(height),
+        } = known_dimensions
+        {
+            return Size { width, height };
+        }
+        let max_width = known_dimensions.width.or(match available_space.width {
+            AvailableSpace::MinContent => Some(0.0),
+            AvailableSpace::MaxContent => None,
+            AvailableSpace::Definite(width) => Some(width),
+        });
+        let measured = match self.cache.iter().find(|(key, _)| *key == max_width) {
+            Some((_, size)) => *size,
+            None => {
+                let size = measurer.measure(&self.text, &self.style, max_width);
+                if self.cache.len() == MEASURE_CACHE_SIZE {
+                    self.cache.remove(0);
+                }
+                self.cache.push((max_width, size));
+                size
+            }
+        };
+        Size {
+            width: known_dimensions.width.unwrap_or(measured.width),
+            height: known_dimensions.height.unwrap_or(measured.height),
+        }
+    }
+}
+
+/// A node context which may hold a [`TextContext`].
+pub trait TextNodeContext: From<TextContext> + 'static {
+    fn as_text(&self) -> Option<&TextContext>;
+    fn as_text_mut(&mut self) -> Option<&mut TextContext>;
+}
+
+impl TextNodeContext for TextContext {
+    #[inline(always)]
+    fn as_text(&self) -> Option<&TextContext> {
+        Some(self)
+    }
+
+    #[inline(always)]
+    fn as_text_mut(&mut self) -> Option<&mut TextContext> {
+        Some(self)
+    }
+}
+
+/// Returns a measure function for `compute_layout_with` which measures text
+/// leaves with the measurer and every other leaf as zero sized.
+#[allow(clippy::type_complexity)]
+pub fn measure_text_with<'a, M, C, S>(
+    measurer: &'a mut M,
+) -> impl FnMut(Size<Option<f32>>, Size<AvailableSpace>, NodeId, Option<&mut C>, &S) -> Size<f32> + 'a
+where
+    M: TextMeasurer + ?Sized,
+    C: TextNodeContext,
+{
+    move |known_dimensions, available_space, _, node_context, _| match node_context
+        .and_then(TextNodeContext::as_text_mut)
+    {
+        Some(text) => text.measure(measurer, known_dimensions, available_space),
+        None => Size::ZERO,
+    }
+}
+
+pub struct Text;
+
+/// A leaf node displaying text, measured by the measure function passed to `compute_layout_with`.
+#[track_caller]
+pub fn text<P, C>(s: Scope<P, C>, style: Style, text: impl Into<String>, text_style: TextStyle)
+where
+    P: 'static,
+    C: TextNodeContext,
+{
+    let style = place_in_parent::<P>(style);
+    let text = text.into();
+    let scope = s.child::<Text>();
+    s.create_node(
+        scope,
+        |_| {},
+        move || (style.clone(), text.clone(), text_style),
+        |(style, text, text_style), _| {
+            LayoutNode::with_context(style, C::from(TextContext::new(text, text_style)))
+        },
+        |n, (style, text, text_style), _| {
+            let mut changed = false;
+            if n.style != style {
+                n.style = style;
+                changed = true;
+            }
+            match n.context.as_mut().and_then(TextNodeContext::as_text_mut) {
+                Some(context) => {
+                    changed |= context.set_text(text);
+                    changed |= context.set_style(text_style);
+                }
+                None => {
+                    n.context = Some(C::from(TextContext::new(text, text_style)));
+                    changed = true;
+                }
+            }
+            if changed {
+                n.mark_dirty();
+            }
+        },
+    );
+}
+
+#[cfg(all(test, feature = "grid"))]
+mod tests {
+    use compose_rt::{Composer, NodeKey, Root};
+    use taffy::style_helpers::TaffyMaxContent;
+    use taffy::{Point, Size};
+
+    use super::*;
+    use crate::impls::TaffyConfig;
+    use crate::modifier::{modifier, StyleModifier};
+    use crate::widgets::{leaf, zstack};
+    use crate::TaffyLayout;
+
+    #[test]
+    fn text_shares_the_zstack_cell() {
+        let app = |s: Scope<Root, TextContext>| {
+            zstack(s, modifier(), |s| {
+                leaf(s, modifier().size(100.0, 50.0));
+                text(s, modifier(), "hello", TextStyle::default());
+            });
+        };
+        let mut recomposer = Composer::compose(app, TaffyConfig::default());
+        let mut measurer = MonospaceMeasurer::default();
+        let stack = recomposer.root_node_key();
+        recomposer
+            .compute_layout_with(Size::MAX_CONTENT, stack, measure_text_with(&mut measurer))
+            .unwrap();
+        let children: Vec<NodeKey> =
+            recomposer.with_composer(|composer| composer.nodes[stack].children.clone());
+        let text_layout = recomposer.layout(children[1]).unwrap();
+        assert_eq!(text_layout.location, Point::ZERO);
+        assert_eq!(recomposer.layout(stack).unwrap().size.height, 50.0);
+    }
+}
@@ -1,5 +1,9 @@
-use compose_rt::{ComposeNode, NodeKey};
-use taffy::{Display, Layout, NodeId};
+use compose_rt::{ComposeNode, NodeKey, State};
+#[cfg(feature = "detailed_layout_info")]
+use taffy::DetailedLayoutInfo;
+use taffy::{AvailableSpace, Display, Layout, NodeId, Point, Size};
+
+use crate::{DisplayFallback, LayoutAnimation, LayoutTransition, PixelSnapping};
 
 pub trait IntoNodeId {
     fn into_node_id(self) -> NodeId;
@@ -23,11 +27,79 @@ impl IntoNodeKey for NodeId {
     }
 }
 
+/// A node context which can measure its own leaf node, used by
+/// `TaffyLayout::compute_layout_measured`.
+pub trait Measure {
+    fn measure(
+        &mut self,
+        known_dimensions: Size<Option<f32>>,
+        available_space: Size<AvailableSpace>,
+    ) -> Size<f32>;
+}
+
+impl Measure for () {
+    #[inline(always)]
+    fn measure(&mut self, _: Size<Option<f32>>, _: Size<AvailableSpace>) -> Size<f32> {
+        Size::ZERO
+    }
+}
+
+impl<M> Measure for Box<M>
+where
+    M: Measure + ?Sized,
+{
+    #[inline(always)]
+    fn measure(
+        &mut self,
+        known_dimensions: Size<Option<f32>>,
+        available_space: Size<AvailableSpace>,
+    ) -> Size<f32> {
+        (**self).measure(known_dimensions, available_space)
+    }
+}
+
+/// A measure function for `TaffyLayout::compute_layout_with` which calls the node's own
+/// [`Measure`] implementation, leaves without a node context are zero sized.
+#[inline(always)]
+pub fn measure_node_context<C, S>(
+    known_dimensions: Size<Option<f32>>,
+    available_space: Size<AvailableSpace>,
+    _: NodeId,
+    node_context: Option<&mut C>,
+    _: &S,
+) -> Size<f32>
+where
+    C: Measure + ?Sized,
+{
+    match node_context {
+        Some(node_context) => node_context.measure(known_dimensions, available_space),
+        None => Size::ZERO,
+    }
+}
+
 pub trait TaffyConfig {
     fn use_rounding(&self) -> bool;
+
+    /// How containers whose layout algorithm is disabled by cargo features are laid out.
+    #[inline(always)]
+    fn display_fallback(&self) -> DisplayFallback {
+        DisplayFallback::default()
+    }
+
+    /// The number of device pixels per logical unit, layouts are snapped to device pixels.
+    #[inline(always)]
+    fn scale_factor(&self) -> f32 {
+        1.0
+    }
+
+    /// How layout values are snapped to the device pixel grid when rounding is enabled.
+    #[inline(always)]
+    fn pixel_snapping(&self) -> PixelSnapping {
+        PixelSnapping::default()
+    }
 }
 
-pub trait TaffyNode: ComposeNode + 'static {
+pub trait TaffyNode: ComposeNode + Sized + 'static {
     type NodeContext;
 
     /// The style type representing the core container styles that all containers should have
@@ -80,6 +152,13 @@ pub trait TaffyNode: ComposeNode + 'static {
     fn set_final_layout(&mut self, layout: &Layout);
     fn get_unrounded_layout(&self) -> &Layout;
     fn set_unrounded_layout(&mut self, layout: &Layout);
+    /// The offset the content of the node is scrolled by, zero for nodes which do not scroll
+    #[inline(always)]
+    fn get_scroll_offset(&self) -> Point<f32> {
+        Point::ZERO
+    }
+    #[inline(always)]
+    fn set_scroll_offset(&mut self, _offset: Point<f32>) {}
     fn cache_get(
         &self,
         known_dimensions: taffy::Size<Option<f32>>,
@@ -94,6 +173,69 @@ pub trait TaffyNode: ComposeNode + 'static {
         layout_output: taffy::LayoutOutput,
     );
     fn cache_clear(&mut self);
+    /// Whether the node changed since the last layout pass, nodes which do not track
+    /// their changes are laid out again in every pass
+    #[inline(always)]
+    fn is_dirty(&self) -> bool {
+        true
+    }
+    #[inline(always)]
+    fn set_dirty(&mut self, _dirty: bool) {}
+    /// The children laid out inside the node as of the last layout pass, which are its
+    /// children without the overlays
+    #[inline(always)]
+    fn get_layout_children(&self) -> &[NodeKey] {
+        &[]
+    }
+    #[inline(always)]
+    fn set_layout_children(&mut self, _children: &[NodeKey]) {}
+    /// The available space of the last layout pass rooted at the node, reset when
+    /// a layout pass rooted elsewhere lays out the node again
+    #[inline(always)]
+    fn get_root_available_space(&self) -> Option<Size<AvailableSpace>> {
+        None
+    }
+    #[inline(always)]
+    fn set_root_available_space(&mut self, _available_space: Option<Size<AvailableSpace>>) {}
+    /// Whether the node is laid out as a separate layout root rather than inside its
+    /// parent, see `widgets::overlay`
+    #[inline(always)]
+    fn is_overlay(&self) -> bool {
+        false
+    }
+    #[inline(always)]
+    fn get_transition(&self) -> Option<LayoutTransition> {
+        None
+    }
+    #[inline(always)]
+    fn get_animation(&self) -> Option<&LayoutAnimation> {
+        None
+    }
+    #[inline(always)]
+    fn set_animation(&mut self, _animation: Option<LayoutAnimation>) {}
+    /// The state to publish the size of the node to
+    #[inline(always)]
+    fn get_size_state(&self) -> Option<State<Size<f32>, Self>> {
+        None
+    }
+    /// The state to publish the space available to the node to
+    #[inline(always)]
+    fn get_constraints_state(&self) -> Option<State<Size<AvailableSpace>, Self>> {
+        None
+    }
+    /// The state to publish the scroll offset of the node to
+    #[inline(always)]
+    fn get_scroll_offset_state(&self) -> Option<State<Point<f32>, Self>> {
+        None
+    }
+    #[cfg(feature = "detailed_layout_info")]
+    #[inline(always)]
+    fn get_detailed_layout_info(&self) -> &DetailedLayoutInfo {
+        &DetailedLayoutInfo::None
+    }
+    #[cfg(feature = "detailed_layout_info")]
+    #[inline(always)]
+    fn set_detailed_layout_info(&mut self, _info: DetailedLayoutInfo) {}
     fn get_core_container_style(&self) -> &Self::CoreContainerStyle;
     #[cfg(feature = "block_layout")]
     fn get_block_container_style(&self) -> Self::BlockContainerStyle<'_>;
@@ -0,0 +1,547 @@
+//! Composable layout primitives built on [`Scope`].
+#[cfg(feature = "grid")]
+use std::any::TypeId;
+use std::cell::Cell;
+#[cfg(feature = "flexbox")]
+use std::ops::Range;
+use std::rc::Rc;
+
+use taffy::style_helpers::TaffyMaxContent;
+use taffy::{AvailableSpace, Overflow, Point, Size, Style};
+#[cfg(feature = "flexbox")]
+use taffy::{Dimension, Display, FlexDirection};
+
+use crate::impls::{LayoutNode, LayoutStates, LayoutStatesSlot, Scope, State};
+pub use crate::text::text;
+use crate::LayoutTransition;
+
+pub struct Container;
+
+/// A node with children, laid out according to its style.
+#[track_caller]
+pub fn container<P, C, F>(s: Scope<P, C>, style: Style, content: F)
+where
+    P: 'static,
+    C: 'static,
+    F: Fn(Scope<Container, C>) + Clone + 'static,
+{
+    let style = place_in_parent::<P>(style);
+    let scope = s.child::<Container>();
+    s.create_node(
+        scope,
+        content,
+        move || style.clone(),
+        |style, _| LayoutNode::new(style),
+        |n, style, _| n.set_style(style),
+    );
+}
+
+pub struct Leaf;
+
+/// A node without children.
+#[track_caller]
+pub fn leaf<P, C>(s: Scope<P, C>, style: Style)
+where
+    P: 'static,
+    C: 'static,
+{
+    let style = place_in_parent::<P>(style);
+    let scope = s.child::<Leaf>();
+    s.create_node(
+        scope,
+        |_| {},
+        move || style.clone(),
+        |style, _| LayoutNode::new(style),
+        |n, style, _| n.set_style(style),
+    );
+}
+
+/// A container whose content scrolls along both axes, the offset is kept by the node
+/// across recompositions and changed with `TaffyLayout::set_scroll_offset`, `scroll_by`
+/// and `scroll_to`.
+#[track_caller]
+pub fn scroll<P, C, F>(s: Scope<P, C>, style: Style, content: F)
+where
+    P: 'static,
+    C: 'static,
+    F: Fn(Scope<Container, C>) + Clone + 'static,
+{
+    let style = Style {
+        overflow: Point {
+            x: Overflow::Scroll,
+            y: Overflow::Scroll,
+        },
+        ..style
+    };
+    container(s, style, content);
+}
+
+/// A container which animates from its previous layout whenever a layout pass changes it,
+/// see `TaffyLayout::animated_layout`.
+#[track_caller]
+pub fn animated<P, C, F>(s: Scope<P, C>, style: Style, transition: LayoutTransition, content: F)
+where
+    P: 'static,
+    C: 'static,
+    F: Fn(Scope<Container, C>) + Clone + 'static,
+{
+    let style = place_in_parent::<P>(style);
+    let scope = s.child::<Container>();
+    s.create_node(
+        scope,
+        content,
+        move || style.clone(),
+        move |style, _| LayoutNode::new(style).with_transition(transition),
+        move |n, style, _| {
+            n.set_style(style);
+            n.transition = Some(transition);
+        },
+    );
+}
+
+/// A container laid out as a separate layout root against the space available to the
+/// root instead of inside its parent, e.g. a dialog, menu or drag preview.
+///
+/// The overlay is placed at the origin of the root, its content is positioned relative
+/// to it. `compute_layout` lays out the overlays after the root in composition order,
+/// which is their z-order, and `hit_test` visits the topmost overlay first.
+#[track_caller]
+pub fn overlay<P, C, F>(s: Scope<P, C>, style: Style, content: F)
+where
+    P: 'static,
+    C: 'static,
+    F: Fn(Scope<Container, C>) + Clone + 'static,
+{
+    let scope = s.child::<Container>();
+    s.create_node(
+        scope,
+        content,
+        move || style.clone(),
+        |style, _| LayoutNode {
+            overlay: true,
+            ..LayoutNode::new(style)
+        },
+        |n, style, _| n.set_style(style),
+    );
+}
+
+/// A container whose content reads the size of the container from the last layout pass.
+///
+/// The state is set by `compute_layout` when the size changes, the content is composed
+/// again by the next `recompose` which should be followed by another layout pass.
+#[track_caller]
+pub fn sized<P, C, F>(s: Scope<P, C>, style: Style, content: F)
+where
+    P: 'static,
+    C: 'static,
+    F: Fn(Scope<Container, C>, State<Size<f32>, C>) + Clone + 'static,
+{
+    observed_container(s, style, move |s, slot: &LayoutStatesSlot<C>| {
+        let size = s.use_state(|| Size::ZERO);
+        slot.set(LayoutStates {
+            size: Some(size),
+            ..slot.get()
+        });
+        content(s, size);
+    });
+}
+
+/// A container whose content is composed with the space available to the container
+/// in the last layout pass, `MaxContent` before the first pass.
+///
+/// Like [`sized`], a change is picked up by the next `recompose`.
+#[track_caller]
+pub fn with_constraints<P, C, F>(s: Scope<P, C>, style: Style, content: F)
+where
+    P: 'static,
+    C: 'static,
+    F: Fn(Scope<Container, C>, Size<AvailableSpace>) + Clone + 'static,
+{
+    observed_container(s, style, move |s, slot: &LayoutStatesSlot<C>| {
+        let constraints = s.use_state(|| Size::MAX_CONTENT);
+        slot.set(LayoutStates {
+            constraints: Some(constraints),
+            ..slot.get()
+        });
+        content(s, constraints.get());
+    });
+}
+
+/// A container sharing a [`LayoutStatesSlot`] with its content, which fills it with
+/// states owned by the container node.
+#[track_caller]
+fn observed_container<P, C, F>(s: Scope<P, C>, style: Style, content: F)
+where
+    P: 'static,
+    C: 'static,
+    F: Fn(Scope<Container, C>, &LayoutStatesSlot<C>) + Clone + 'static,
+{
+    let style = place_in_parent::<P>(style);
+    let slot: LayoutStatesSlot<C> = Rc::new(Cell::new(LayoutStates::default()));
+    let content_slot = slot.clone();
+    let scope = s.child::<Container>();
+    s.create_node(
+        scope,
+        move |s| content(s, &content_slot),
+        move || (style.clone(), slot.clone()),
+        |(style, slot), _| LayoutNode {
+            layout_states: Some(slot),
+            ..LayoutNode::new(style)
+        },
+        |n, (style, slot), _| {
+            n.set_style(style);
+            n.layout_states = Some(slot);
+        },
+    );
+}
+
+/// A flexbox container laying out its children horizontally.
+#[cfg(feature = "flexbox")]
+#[track_caller]
+pub fn row<P, C, F>(s: Scope<P, C>, style: Style, content: F)
+where
+    P: 'static,
+    C: 'static,
+    F: Fn(Scope<Container, C>) + Clone + 'static,
+{
+    let style = Style {
+        display: Display::Flex,
+        flex_direction: FlexDirection::Row,
+        ..style
+    };
+    container(s, style, content);
+}
+
+/// A flexbox container laying out its children vertically.
+#[cfg(feature = "flexbox")]
+#[track_caller]
+pub fn column<P, C, F>(s: Scope<P, C>, style: Style, content: F)
+where
+    P: 'static,
+    C: 'static,
+    F: Fn(Scope<Container, C>) + Clone + 'static,
+{
+    let style = Style {
+        display: Display::Flex,
+        flex_direction: FlexDirection::Column,
+        ..style
+    };
+    container(s, style, content);
+}
+
+/// A CSS Grid container, the tracks are taken from the style.
+#[cfg(feature = "grid")]
+#[track_caller]
+pub fn grid<P, C, F>(s: Scope<P, C>, style: Style, content: F)
+where
+    P: 'static,
+    C: 'static,
+    F: Fn(Scope<Container, C>) + Clone + 'static,
+{
+    let style = Style {
+        display: taffy::Display::Grid,
+        ..style
+    };
+    container(s, style, content);
+}
+
+#[cfg(feature = "grid")]
+pub struct ZStack;
+
+/// A container stacking its children on top of each other, later children on top.
+///
+/// The stack is a single cell grid, the widgets of this module composed directly
+/// inside it are placed into that cell.
+#[cfg(feature = "grid")]
+#[track_caller]
+pub fn zstack<P, C, F>(s: Scope<P, C>, style: Style, content: F)
+where
+    P: 'static,
+    C: 'static,
+    F: Fn(Scope<ZStack, C>) + Clone + 'static,
+{
+    let style = place_in_parent::<P>(Style {
+        display: taffy::Display::Grid,
+        ..style
+    });
+    let scope = s.child::<ZStack>();
+    s.create_node(
+        scope,
+        content,
+        move || style.clone(),
+        |style, _| LayoutNode::new(style),
+        |n, style, _| n.set_style(style),
+    );
+}
+
+/// An empty leaf which grows to fill the free space of a flexbox container.
+#[cfg(feature = "flexbox")]
+#[track_caller]
+pub fn spacer<P, C>(s: Scope<P, C>)
+where
+    P: 'static,
+    C: 'static,
+{
+    let style = Style {
+        flex_grow: 1.0,
+        ..Default::default()
+    };
+    leaf(s, style);
+}
+
+/// A scrolling flexbox column which only composes the items in view, see [`lazy_list`].
+#[cfg(feature = "flexbox")]
+#[track_caller]
+pub fn lazy_column<P, C, F>(
+    s: Scope<P, C>,
+    style: Style,
+    item_count: usize,
+    estimated_item_size: f32,
+    item: F,
+) where
+    P: 'static,
+    C: 'static,
+    F: Fn(Scope<Container, C>, usize) + Clone + 'static,
+{
+    lazy_list(
+        s,
+        style,
+        FlexDirection::Column,
+        item_count,
+        estimated_item_size,
+        item,
+    );
+}
+
+/// A scrolling flexbox row which only composes the items in view, see [`lazy_list`].
+#[cfg(feature = "flexbox")]
+#[track_caller]
+pub fn lazy_row<P, C, F>(
+    s: Scope<P, C>,
+    style: Style,
+    item_count: usize,
+    estimated_item_size: f32,
+    item: F,
+) where
+    P: 'static,
+    C: 'static,
+    F: Fn(Scope<Container, C>, usize) + Clone + 'static,
+{
+    lazy_list(
+        s,
+        style,
+        FlexDirection::Row,
+        item_count,
+        estimated_item_size,
+        item,
+    );
+}
+
+/// The number of items composed beyond each edge of the viewport.
+#[cfg(feature = "flexbox")]
+const LAZY_LIST_OVERSCAN: usize = 1;
+
+/// A scrolling flexbox container which only composes the items overlapping its viewport.
+///
+/// The window of items is derived from the size and scroll offset of the container in the
+/// last layout pass, the items outside of it are replaced by spacers sized by the
+/// estimated item size. The content is composed again when the size or the scroll offset
+/// changes, so scrolling is followed by `recompose` and another layout pass. Items are
+/// keyed by their index and should not shrink along the main axis.
+///
+/// The window is sized for items exactly `estimated_item_size` long along the main axis,
+/// so items should be at least that long, smaller items leave the end of the viewport
+/// empty.
+#[cfg(feature = "flexbox")]
+#[track_caller]
+pub fn lazy_list<P, C, F>(
+    s: Scope<P, C>,
+    style: Style,
+    direction: FlexDirection,
+    item_count: usize,
+    estimated_item_size: f32,
+    item: F,
+) where
+    P: 'static,
+    C: 'static,
+    F: Fn(Scope<Container, C>, usize) + Clone + 'static,
+{
+    let horizontal = matches!(direction, FlexDirection::Row | FlexDirection::RowReverse);
+    let mut overflow = style.overflow;
+    if horizontal {
+        overflow.x = Overflow::Scroll;
+    } else {
+        overflow.y = Overflow::Scroll;
+    }
+    let style = Style {
+        display: Display::Flex,
+        flex_direction: direction,
+        overflow,
+        ..style
+    };
+    let estimated_item_size = estimated_item_size.max(1.0);
+    observed_container(s, style, move |s, slot: &LayoutStatesSlot<C>| {
+        let size = s.use_state(|| Size::ZERO);
+        let scroll_offset = s.use_state(|| Point::ZERO);
+        slot.set(LayoutStates {
+            size: Some(size),
+            scroll_offset: Some(scroll_offset),
+            ..slot.get()
+        });
+        let window = move || {
+            let (size, scroll_offset) = (size.get(), scroll_offset.get());
+            let (viewport, offset) = if horizontal {
+                (size.width, scroll_offset.x)
+            } else {
+                (size.height, scroll_offset.y)
+            };
+            lazy_list_window(viewport, offset, estimated_item_size, item_count)
+        };
+        let estimated_length = move |count: usize| count as f32 * estimated_item_size;
+        lazy_list_gap(s, horizontal, move || estimated_length(window().start));
+        for index in window() {
+            let item = item.clone();
+            s.key(index, move |s| item(s, index));
+        }
+        lazy_list_gap(s, horizontal, move || {
+            estimated_length(item_count - window().end)
+        });
+    });
+}
+
+/// The indices of the items of a lazy list overlapping the viewport along the main axis,
+/// extended by `LAZY_LIST_OVERSCAN` items on both sides.
+#[cfg(feature = "flexbox")]
+fn lazy_list_window(
+    viewport: f32,
+    offset: f32,
+    estimated_item_size: f32,
+    item_count: usize,
+) -> Range<usize> {
+    let first = (offset / estimated_item_size) as usize;
+    let visible = (viewport / estimated_item_size).ceil() as usize + 1;
+    let start = first.saturating_sub(LAZY_LIST_OVERSCAN).min(item_count);
+    let end = (first + visible + LAZY_LIST_OVERSCAN).min(item_count);
+    start..end
+}
+
+/// Stands in for the items of a lazy list outside of its window. The length is read
+/// when the node is composed, so the gap follows the states the length depends on.
+#[cfg(feature = "flexbox")]
+#[track_caller]
+fn lazy_list_gap<C, L>(s: Scope<Container, C>, horizontal: bool, length: L)
+where
+    C: 'static,
+    L: Fn() -> f32 + Clone + 'static,
+{
+    let scope = s.child::<Leaf>();
+    s.create_node(
+        scope,
+        |_| {},
+        move || {
+            let length = Dimension::Length(length());
+            let size = if horizontal {
+                Size {
+                    width: length,
+                    height: Dimension::Auto,
+                }
+            } else {
+                Size {
+                    width: Dimension::Auto,
+                    height: length,
+                }
+            };
+            Style {
+                size,
+                flex_shrink: 0.0,
+                ..Default::default()
+            }
+        },
+        |style, _| LayoutNode::new(style),
+        |n, style, _| n.set_style(style),
+    );
+}
+
+/// Applies the placement required by the parent scope.
+#[inline(always)]
+#[cfg_attr(not(feature = "grid"), allow(clippy::extra_unused_type_parameters))]
+pub(crate) fn place_in_parent<P>(style: Style) -> Style
+where
+    P: 'static,
+{
+    #[cfg(feature = "grid")]
+    if TypeId::of::<P>() == TypeId::of::<ZStack>() {
+        return Style {
+            grid_row: taffy::style_helpers::line(1),
+            grid_column: taffy::style_helpers::line(1),
+            ..style
+        };
+    }
+    style
+}
+
+#[cfg(all(test, feature = "flexbox"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn window_covers_the_viewport_and_the_overscan() {
+        // five items fit, a sixth is partially visible when scrolled
+        assert_eq!(lazy_list_window(100.0, 0.0, 20.0, 100), 0..7);
+        assert_eq!(lazy_list_window(100.0, 50.0, 20.0, 100), 1..9);
+        assert_eq!(lazy_list_window(100.0, 60.0, 20.0, 100), 2..10);
+    }
+
+    #[test]
+    fn window_is_clamped_to_the_items() {
+        assert_eq!(lazy_list_window(100.0, 180.0, 20.0, 10), 8..10);
+        assert_eq!(lazy_list_window(100.0, 1000.0, 20.0, 10), 10..10);
+        assert_eq!(lazy_list_window(100.0, 0.0, 20.0, 3), 0..3);
+        assert_eq!(lazy_list_window(100.0, 0.0, 20.0, 0), 0..0);
+    }
+
+    #[test]
+    fn window_before_the_first_layout_holds_a_single_item() {
+        assert_eq!(lazy_list_window(0.0, 0.0, 20.0, 100), 0..2);
+    }
+
+    #[cfg(feature = "content_size")]
+    #[test]
+    fn lazy_column_composes_the_window_between_gaps() {
+        use compose_rt::{Composer, Recomposer, Root};
+
+        use crate::impls::TaffyConfig;
+        use crate::modifier::{modifier, StyleModifier};
+        use crate::test_utils::{children, point, SPACE};
+        use crate::TaffyLayout;
+
+        fn app(s: Scope<Root, ()>) {
+            lazy_column(s, modifier().size(100.0, 100.0), 100, 20.0, |s, _| {
+                leaf(s, modifier().height(20.0).flex_shrink(0.0));
+            });
+        }
+        let mut recomposer = Composer::compose(app, TaffyConfig::default());
+        let root = recomposer.root_node_key();
+        let content_heights = |recomposer: &mut Recomposer<(), LayoutNode<()>>| {
+            recomposer.compute_layout(SPACE).unwrap();
+            recomposer.recompose();
+            recomposer.compute_layout(SPACE).unwrap();
+            children(recomposer, root)
+                .into_iter()
+                .map(|child| recomposer.layout(child).unwrap().size.height)
+                .collect::<Vec<_>>()
+        };
+        let mut expected = vec![0.0];
+        expected.extend([20.0; 7]);
+        expected.push(93.0 * 20.0);
+        assert_eq!(content_heights(&mut recomposer), expected);
+
+        recomposer
+            .set_scroll_offset(root, point(0.0, 50.0))
+            .unwrap();
+        let mut expected = vec![20.0];
+        expected.extend([20.0; 8]);
+        expected.push(91.0 * 20.0);
+        assert_eq!(content_heights(&mut recomposer), expected);
+    }
+}